@@ -0,0 +1,113 @@
+//! Per-method serve-time budgets with deadline enforcement
+//!
+//! Each RPC method is assigned an expected (deliberately overestimated) serve
+//! duration. Handlers fail fast once they exceed their budget instead of
+//! letting a single expensive call hold resources indefinitely, and observed
+//! durations feed back into the table so it converges toward real behavior
+//! instead of staying a static guess forever.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Budget applied to any method without a configured or learned entry
+const DEFAULT_BUDGET: Duration = Duration::from_secs(5);
+
+/// Seed table of per-method serve-time budgets, in milliseconds
+///
+/// These are deliberately generous starting points; [`MethodBudgets::record_observed_ms`]
+/// widens them when an observed duration exceeds them, and never narrows them.
+fn default_budgets_ms() -> HashMap<&'static str, u64> {
+    HashMap::from([("SayHello", 250), ("StreamTime", 2000)])
+}
+
+/// Tracks and refines per-method serve-time budgets
+///
+/// Seeded from a hardcoded overestimate, optionally overridden via config,
+/// and widened over time as real durations are observed.
+#[derive(Debug)]
+pub struct MethodBudgets {
+    budgets_ms: RwLock<HashMap<String, u64>>,
+}
+
+impl MethodBudgets {
+    /// Creates a budget table seeded with hardcoded defaults, overridden by
+    /// any entries in `overrides` (method name -> budget in milliseconds)
+    pub fn new(overrides: &HashMap<String, u64>) -> Self {
+        let mut budgets_ms: HashMap<String, u64> = default_budgets_ms()
+            .into_iter()
+            .map(|(method, ms)| (method.to_string(), ms))
+            .collect();
+        budgets_ms.extend(overrides.iter().map(|(method, ms)| (method.clone(), *ms)));
+
+        Self {
+            budgets_ms: RwLock::new(budgets_ms),
+        }
+    }
+
+    /// Returns the current budget for `method`, falling back to [`DEFAULT_BUDGET`]
+    pub fn budget_for(&self, method: &str) -> Duration {
+        self.budgets_ms
+            .read()
+            .unwrap()
+            .get(method)
+            .map(|ms| Duration::from_millis(*ms))
+            .unwrap_or(DEFAULT_BUDGET)
+    }
+
+    /// Feeds an observed serve duration back into the table, widening the
+    /// budget for `method` if the observation exceeds it.
+    ///
+    /// Only ever widens: a single slow-but-legitimate call should stop
+    /// getting flagged, but a budget should never shrink out from under
+    /// calls that were previously within it.
+    pub fn record_observed_ms(&self, method: &str, observed_ms: u64) {
+        let mut budgets = self.budgets_ms.write().unwrap();
+        let current = budgets
+            .get(method)
+            .copied()
+            .unwrap_or(DEFAULT_BUDGET.as_millis() as u64);
+
+        if observed_ms > current {
+            budgets.insert(method.to_string(), observed_ms);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_budget_for_unknown_method() {
+        let budgets = MethodBudgets::new(&HashMap::new());
+        assert_eq!(budgets.budget_for("Unknown"), DEFAULT_BUDGET);
+    }
+
+    #[test]
+    fn test_seed_budget_for_known_method() {
+        let budgets = MethodBudgets::new(&HashMap::new());
+        assert_eq!(budgets.budget_for("SayHello"), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_override_replaces_seed_budget() {
+        let overrides = HashMap::from([("SayHello".to_string(), 1000)]);
+        let budgets = MethodBudgets::new(&overrides);
+        assert_eq!(budgets.budget_for("SayHello"), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_record_observed_widens_budget() {
+        let budgets = MethodBudgets::new(&HashMap::new());
+        budgets.record_observed_ms("SayHello", 5000);
+        assert_eq!(budgets.budget_for("SayHello"), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_record_observed_does_not_narrow_budget() {
+        let budgets = MethodBudgets::new(&HashMap::new());
+        budgets.record_observed_ms("SayHello", 10);
+        assert_eq!(budgets.budget_for("SayHello"), Duration::from_millis(250));
+    }
+}
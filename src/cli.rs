@@ -0,0 +1,139 @@
+use clap::Parser;
+
+use crate::config::{ConfigFormat, LogFormat, LogLevel};
+
+/// Command-line overrides for the layered configuration.
+///
+/// Sits above environment variables in [`crate::config::load_config_with_cli`]'s
+/// precedence chain (defaults < file < env < CLI), mirroring the CLI surface
+/// used by mangadex-home and bunbun.
+#[derive(Debug, Clone, Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Path to the config file (without extension), overriding the default `config/settings`
+    #[arg(short = 'c', long = "config", default_value = "config/settings")]
+    pub config: String,
+
+    /// Increase log verbosity; repeatable (-v towards debug, -vv towards trace).
+    /// Mutually exclusive with --quiet.
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        action = clap::ArgAction::Count,
+        conflicts_with = "quiet"
+    )]
+    pub verbose: u8,
+
+    /// Decrease log verbosity; repeatable (-q towards warn, -qq towards error).
+    /// Mutually exclusive with --verbose.
+    #[arg(
+        short = 'q',
+        long = "quiet",
+        action = clap::ArgAction::Count,
+        conflicts_with = "verbose"
+    )]
+    pub quiet: u8,
+
+    /// Override the gRPC listen address (host:port)
+    #[arg(long = "grpc-address")]
+    pub grpc_address: Option<String>,
+
+    /// Override the log output format
+    #[arg(long = "log-format", value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Print the fully-resolved configuration (after defaults/file/env/CLI merging)
+    /// to stdout and exit, without starting the server. Lets operators and
+    /// integration tests verify the effective config end-to-end.
+    #[arg(long = "dump-config", hide = true, value_enum)]
+    pub dump_config: Option<ConfigFormat>,
+
+    /// Perform the full load/validate/startup path, then return before entering
+    /// the serve loop. Lets integration tests smoke-test startup without
+    /// leaving a server running.
+    #[arg(long = "immediate-shutdown", hide = true)]
+    pub immediate_shutdown: bool,
+
+    /// On-disk format to use when writing the config file with `--init`,
+    /// instead of being locked to whatever `config::File` auto-detects.
+    #[arg(long = "config-format", value_enum, default_value = "toml")]
+    pub config_format: ConfigFormat,
+
+    /// If no config file exists at `--config` under any extension
+    /// `config::File` auto-detects, write `AppConfig::default()` to `--config`
+    /// plus `--config-format`'s extension and exit, so first-run users get a
+    /// discoverable starting point
+    /// instead of relying entirely on env vars.
+    #[arg(long = "init")]
+    pub init: bool,
+}
+
+impl Cli {
+    /// Applies this CLI's `-v`/`-q` occurrence counts to `base`, stepping one
+    /// level towards `Trace` per `-v` and one level towards `Error` per `-q`,
+    /// clamped at either end. `-v` and `-q` are mutually exclusive, so at most
+    /// one of them is ever nonzero.
+    pub fn adjust_log_level(&self, base: &LogLevel) -> LogLevel {
+        const LEVELS: [LogLevel; 5] = [
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+        ];
+
+        let base_index = LEVELS.iter().position(|level| level == base).unwrap_or(2) as i64;
+        let shift = self.verbose as i64 - self.quiet as i64;
+        let index = (base_index - shift).clamp(0, LEVELS.len() as i64 - 1) as usize;
+
+        LEVELS[index].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cli_with(verbose: u8, quiet: u8) -> Cli {
+        Cli {
+            config: "config/settings".to_string(),
+            verbose,
+            quiet,
+            grpc_address: None,
+            log_format: None,
+            dump_config: None,
+            immediate_shutdown: false,
+            config_format: ConfigFormat::Toml,
+            init: false,
+        }
+    }
+
+    #[test]
+    fn test_adjust_log_level_no_flags_is_unchanged() {
+        assert_eq!(cli_with(0, 0).adjust_log_level(&LogLevel::Info), LogLevel::Info);
+    }
+
+    #[test]
+    fn test_adjust_log_level_verbose_steps_towards_trace() {
+        let cli = cli_with(2, 0);
+        assert_eq!(cli.adjust_log_level(&LogLevel::Info), LogLevel::Trace);
+    }
+
+    #[test]
+    fn test_adjust_log_level_quiet_steps_towards_error() {
+        let cli = cli_with(0, 2);
+        assert_eq!(cli.adjust_log_level(&LogLevel::Info), LogLevel::Error);
+    }
+
+    #[test]
+    fn test_adjust_log_level_clamps_at_bounds() {
+        assert_eq!(
+            cli_with(10, 0).adjust_log_level(&LogLevel::Info),
+            LogLevel::Trace
+        );
+        assert_eq!(
+            cli_with(0, 10).adjust_log_level(&LogLevel::Info),
+            LogLevel::Error
+        );
+    }
+}
@@ -1,12 +1,15 @@
+use anyhow::Context;
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fmt::{self, Display},
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     str::FromStr,
+    time::Duration,
 };
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, clap::ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Pretty,
@@ -71,21 +74,218 @@ impl FromStr for LogLevel {
     }
 }
 
+/// A TCP port restricted to the unprivileged range (`>= 1024`), so the health
+/// server can't be misconfigured to require root or collide with a
+/// well-known port. Enforced in [`Port`]'s `Deserialize` impl, so a bad value
+/// fails with a field-scoped error straight out of `try_deserialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Port(u16);
+
+impl Port {
+    pub const MIN: u16 = 1024;
+
+    pub fn get(self) -> u16 {
+        self.0
+    }
+}
+
+impl TryFrom<u16> for Port {
+    type Error = String;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value < Self::MIN {
+            Err(format!("port must be >= {}, got {value}", Self::MIN))
+        } else {
+            Ok(Port(value))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Port {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Port::try_from(u16::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Display for Port {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A non-zero connection/stream count, capped at [`Self::MAX`]. Replaces a
+/// hand-written `0 < n <= 10000` check in `AppConfig::validate()` with one
+/// enforced wherever a `MaxConnections` is constructed or deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct MaxConnections(std::num::NonZeroU32);
+
+impl MaxConnections {
+    pub const MAX: u32 = 10_000;
+
+    pub fn get(self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl TryFrom<u32> for MaxConnections {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        let value = std::num::NonZeroU32::new(value)
+            .ok_or_else(|| "max_connections must be > 0, got 0".to_string())?;
+
+        if value.get() > Self::MAX {
+            return Err(format!(
+                "max_connections too large (max {}), got: {}",
+                Self::MAX,
+                value.get()
+            ));
+        }
+
+        Ok(MaxConnections(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for MaxConnections {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        MaxConnections::try_from(u32::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A non-zero duration, declared in seconds at the config layer and bounded
+/// to at most `MAX_SECS`. Shared by [`StreamingConfig`]'s `interval_seconds`
+/// (`MAX_SECS` = 3600, 1h) and `timeout_seconds` (`MAX_SECS` = 86400, 24h),
+/// so a zero or absurdly large value fails during `try_deserialize` instead
+/// of a generic `AppConfig::validate()` bail later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundedDurationSecs<const MAX_SECS: u64>(Duration);
+
+impl<const MAX_SECS: u64> BoundedDurationSecs<MAX_SECS> {
+    pub fn as_duration(self) -> Duration {
+        self.0
+    }
+
+    pub fn as_secs(self) -> u64 {
+        self.0.as_secs()
+    }
+}
+
+impl<const MAX_SECS: u64> TryFrom<u64> for BoundedDurationSecs<MAX_SECS> {
+    type Error = String;
+
+    fn try_from(secs: u64) -> Result<Self, Self::Error> {
+        if secs == 0 {
+            return Err("must be > 0 seconds, got 0".to_string());
+        }
+
+        if secs > MAX_SECS {
+            return Err(format!("too large (max {MAX_SECS}s), got: {secs}s"));
+        }
+
+        Ok(Self(Duration::from_secs(secs)))
+    }
+}
+
+impl<const MAX_SECS: u64> Serialize for BoundedDurationSecs<MAX_SECS> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.0.as_secs())
+    }
+}
+
+impl<'de, const MAX_SECS: u64> Deserialize<'de> for BoundedDurationSecs<MAX_SECS> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::try_from(u64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// `StreamingConfig::interval_seconds`' newtype: 1 second to 1 hour.
+pub type StreamingIntervalSeconds = BoundedDurationSecs<3600>;
+/// `StreamingConfig::timeout_seconds`' newtype: 1 second to 24 hours.
+pub type StreamingTimeoutSeconds = BoundedDurationSecs<86400>;
+
 /// Main application configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub logging: LoggingConfig,
     pub streaming: StreamingConfig,
+    pub budgets: BudgetConfig,
+    pub greeter: GreeterConfig,
 }
 
 /// Server-related configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ServerConfig {
     pub grpc_address: String,
-    pub health_port: u16,
+    pub health_port: Port,
+    /// When set, the server shuts down gracefully once it has observed zero
+    /// active gRPC connections/streams for this many consecutive seconds.
+    /// Useful for on-demand (e.g. socket-activated or ephemeral test) deployments.
+    #[serde(default)]
+    pub shutdown_after_idle_seconds: Option<u64>,
+    /// Maximum size (bytes) of a decoded inbound message. `None` uses tonic's default.
+    #[serde(default)]
+    pub max_decoding_message_size: Option<usize>,
+    /// Maximum size (bytes) of an encoded outbound message. `None` uses tonic's default.
+    #[serde(default)]
+    pub max_encoding_message_size: Option<usize>,
+    /// Initial HTTP/2 per-stream flow-control window size. `None` uses the transport default.
+    #[serde(default)]
+    pub http2_initial_stream_window_size: Option<u32>,
+    /// Initial HTTP/2 per-connection flow-control window size. `None` uses the transport default.
+    #[serde(default)]
+    pub http2_initial_connection_window_size: Option<u32>,
+    /// IP addresses of upstream load balancers/proxies allowed to prefix
+    /// connections with a PROXY protocol (v1 or v2) header. A connection from
+    /// any other peer has its leading bytes passed through untouched, so an
+    /// untrusted client can't spoof `client_addr` by sending a fake header.
+    /// Empty (the default) disables PROXY protocol support entirely.
+    #[serde(default)]
+    pub proxy_protocol_trusted_upstreams: Vec<IpAddr>,
+    /// TLS/mTLS configuration. Disabled (plaintext) by default.
+    #[serde(default)]
+    pub tls: TlsConfig,
+}
+
+/// TLS/mTLS configuration for the gRPC server
+///
+/// Disabled (plaintext) unless `enabled` is set. When enabled, `cert_path`
+/// and `key_path` must point to a PEM-encoded certificate/key pair; setting
+/// `client_ca_path` additionally turns on mutual TLS, verifying client
+/// certificates against it.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TlsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub cert_path: Option<String>,
+    #[serde(default)]
+    pub key_path: Option<String>,
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
 }
 
+/// Minimum allowed value for configurable message-size limits (1 KiB)
+const MIN_MESSAGE_SIZE_BYTES: usize = 1024;
+/// Maximum allowed value for configurable message-size limits (1 GiB)
+const MAX_MESSAGE_SIZE_BYTES: usize = 1024 * 1024 * 1024;
+/// HTTP/2 flow-control windows cannot exceed 2^31 - 1 per the HTTP/2 spec
+const MAX_HTTP2_WINDOW_SIZE: u32 = (1u32 << 31) - 1;
+
 /// Logging-related configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LoggingConfig {
@@ -96,9 +296,96 @@ pub struct LoggingConfig {
 /// Streaming-related configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct StreamingConfig {
-    pub interval_seconds: u64,
-    pub max_connections: u32,
-    pub timeout_seconds: u64,
+    pub interval_seconds: StreamingIntervalSeconds,
+    pub max_connections: MaxConnections,
+    pub timeout_seconds: StreamingTimeoutSeconds,
+    /// Minimum sustained throughput (messages/sec) a `StreamTime` subscriber must
+    /// keep draining. Below this floor for `stall_grace_period_seconds`, the stream
+    /// is aborted. `None` disables stalled-stream protection.
+    #[serde(default)]
+    pub stall_min_throughput_per_sec: Option<f64>,
+    /// How long throughput may stay below `stall_min_throughput_per_sec` before the
+    /// stream is aborted. Only meaningful when the floor is set.
+    #[serde(default)]
+    pub stall_grace_period_seconds: Option<u64>,
+    /// How long a disconnected `StreamTime` subscription's resume state (sequence
+    /// number, last-active time) is retained. A `resume_token` presented after
+    /// this window has elapsed mints a fresh stream instead of continuing the old one.
+    pub resume_ttl_seconds: u64,
+}
+
+impl StreamingConfig {
+    /// Field names that are safe to hot-swap into a running server without a
+    /// restart -- pure scheduling/admission knobs with no in-flight state tied
+    /// to their old values. Everything else here (stall-detection knobs,
+    /// resume TTL) is threaded into `GreeterService` at construction time and
+    /// needs a restart to change safely.
+    pub const LIVE_SAFE_FIELDS: &'static [&'static str] =
+        &["interval_seconds", "max_connections", "timeout_seconds"];
+
+    /// Copies only the [`Self::LIVE_SAFE_FIELDS`] from `new` into `self`,
+    /// leaving everything else untouched.
+    pub fn apply_live_safe(&mut self, new: &StreamingConfig) {
+        self.interval_seconds = new.interval_seconds;
+        self.max_connections = new.max_connections;
+        self.timeout_seconds = new.timeout_seconds;
+    }
+}
+
+/// `GreeterService`-specific configuration: message-size ceilings, the
+/// service-wide concurrent-stream cap, and the default `StreamTime` interval
+/// a client gets when it doesn't request one.
+///
+/// Distinct from [`StreamingConfig::max_connections`], which only gates new
+/// `StreamTime` admissions via a live atomic cap: `max_concurrent_streams` here
+/// is checked against `active_streams` when a `StreamTime` stream is set up,
+/// giving a second, service-wide safety net. Only `StreamTime` streams are
+/// counted and capped; `StreamGreetings` calls are neither.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GreeterConfig {
+    /// Maximum size (bytes) of a decoded inbound message, applied directly to
+    /// the `GreeterServer` wrapper. Overridden by `server.max_decoding_message_size`
+    /// when that's set.
+    pub max_decoding_message_size: usize,
+    /// Maximum size (bytes) of an encoded outbound message, applied directly to
+    /// the `GreeterServer` wrapper. Overridden by `server.max_encoding_message_size`
+    /// when that's set.
+    pub max_encoding_message_size: usize,
+    /// Maximum number of active `StreamTime` streams the service will admit at
+    /// once; `StreamTime` requests beyond this are rejected with
+    /// `RESOURCE_EXHAUSTED`. `StreamGreetings` calls are not counted or limited.
+    pub max_concurrent_streams: u32,
+    /// Default `StreamTime` emission interval, in seconds, used when a client's
+    /// request doesn't specify `interval_millis`.
+    pub default_interval_seconds: u64,
+    /// Artificial delay, in milliseconds, injected into `SayHello` and `StreamTime`
+    /// setup before doing any other work. Exists purely to drive deadline-cancellation
+    /// tests (see `tests/common.rs::run_with_deadline`); zero (the default) disables it.
+    #[serde(default)]
+    pub artificial_handler_delay_ms: u64,
+}
+
+impl Default for GreeterConfig {
+    fn default() -> Self {
+        Self {
+            max_decoding_message_size: 4 * 1024 * 1024,
+            max_encoding_message_size: 4 * 1024 * 1024,
+            max_concurrent_streams: 1000,
+            default_interval_seconds: 1,
+            artificial_handler_delay_ms: 0,
+        }
+    }
+}
+
+/// Per-method serve-time budget configuration
+///
+/// Overrides the hardcoded defaults in [`crate::budget::MethodBudgets`]; methods
+/// without an entry here keep using their built-in seed budget.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct BudgetConfig {
+    /// Per-method serve-time budget, in milliseconds, keyed by RPC method name (e.g. "SayHello")
+    #[serde(default)]
+    pub overrides_ms: HashMap<String, u64>,
 }
 
 impl Default for AppConfig {
@@ -106,17 +393,32 @@ impl Default for AppConfig {
         Self {
             server: ServerConfig {
                 grpc_address: "127.0.0.1:50051".to_string(),
-                health_port: 8081,
+                health_port: Port::try_from(8081u16).expect("default health port is valid"),
+                shutdown_after_idle_seconds: None,
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+                http2_initial_stream_window_size: None,
+                http2_initial_connection_window_size: None,
+                proxy_protocol_trusted_upstreams: Vec::new(),
+                tls: TlsConfig::default(),
             },
             logging: LoggingConfig {
                 level: LogLevel::Info,
                 format: LogFormat::Pretty,
             },
             streaming: StreamingConfig {
-                interval_seconds: 1,
-                max_connections: 100,
-                timeout_seconds: 300,
+                interval_seconds: StreamingIntervalSeconds::try_from(1u64)
+                    .expect("default interval_seconds is valid"),
+                max_connections: MaxConnections::try_from(100u32)
+                    .expect("default max_connections is valid"),
+                timeout_seconds: StreamingTimeoutSeconds::try_from(300u64)
+                    .expect("default timeout_seconds is valid"),
+                stall_min_throughput_per_sec: None,
+                stall_grace_period_seconds: None,
+                resume_ttl_seconds: 300,
             },
+            budgets: BudgetConfig::default(),
+            greeter: GreeterConfig::default(),
         }
     }
 }
@@ -132,54 +434,282 @@ impl AppConfig {
                 anyhow::anyhow!("Invalid gRPC address '{}': {}", self.server.grpc_address, e)
             })?;
 
-        // Validate health port is in valid range
-        if self.server.health_port < 1024 {
-            anyhow::bail!(
-                "Health port must be >= 1024, got: {}",
-                self.server.health_port
-            );
+        // Health port's `>= 1024` bound is enforced by the `Port` type itself.
+
+        // Validate idle-shutdown threshold, if configured
+        if let Some(idle_seconds) = self.server.shutdown_after_idle_seconds {
+            if idle_seconds == 0 {
+                anyhow::bail!("shutdown_after_idle_seconds must be > 0 when set, got: 0");
+            }
         }
 
-        // Validate streaming configuration
-        if self.streaming.interval_seconds == 0 {
-            anyhow::bail!("Streaming interval must be > 0 seconds, got: 0");
+        // Validate configurable message-size limits, if set
+        for (name, size) in [
+            (
+                "max_decoding_message_size",
+                self.server.max_decoding_message_size,
+            ),
+            (
+                "max_encoding_message_size",
+                self.server.max_encoding_message_size,
+            ),
+        ] {
+            if let Some(size) = size {
+                if !(MIN_MESSAGE_SIZE_BYTES..=MAX_MESSAGE_SIZE_BYTES).contains(&size) {
+                    anyhow::bail!(
+                        "{} must be between {} and {} bytes, got: {}",
+                        name,
+                        MIN_MESSAGE_SIZE_BYTES,
+                        MAX_MESSAGE_SIZE_BYTES,
+                        size
+                    );
+                }
+            }
         }
 
-        if self.streaming.interval_seconds > 3600 {
-            anyhow::bail!(
-                "Streaming interval too large (max 3600s/1h), got: {}s",
-                self.streaming.interval_seconds
-            );
+        // Validate HTTP/2 flow-control window sizes, if set
+        for (name, window) in [
+            (
+                "http2_initial_stream_window_size",
+                self.server.http2_initial_stream_window_size,
+            ),
+            (
+                "http2_initial_connection_window_size",
+                self.server.http2_initial_connection_window_size,
+            ),
+        ] {
+            if let Some(window) = window {
+                if window == 0 || window > MAX_HTTP2_WINDOW_SIZE {
+                    anyhow::bail!(
+                        "{} must be between 1 and {} bytes, got: {}",
+                        name,
+                        MAX_HTTP2_WINDOW_SIZE,
+                        window
+                    );
+                }
+            }
         }
 
-        if self.streaming.max_connections == 0 {
-            anyhow::bail!("Max connections must be > 0, got: 0");
+        // `streaming.interval_seconds`, `max_connections`, and `timeout_seconds`'
+        // bounds are enforced by their respective newtypes.
+
+        // Validate stalled-stream protection, if configured
+        if let Some(floor) = self.streaming.stall_min_throughput_per_sec {
+            if !(floor > 0.0 && floor.is_finite()) {
+                anyhow::bail!(
+                    "stall_min_throughput_per_sec must be > 0 when set, got: {}",
+                    floor
+                );
+            }
         }
 
-        if self.streaming.max_connections > 10000 {
-            anyhow::bail!(
-                "Max connections too large (max 10000), got: {}",
-                self.streaming.max_connections
-            );
+        if let Some(grace_seconds) = self.streaming.stall_grace_period_seconds {
+            if grace_seconds == 0 {
+                anyhow::bail!("stall_grace_period_seconds must be > 0 when set, got: 0");
+            }
+        }
+
+        if self.streaming.resume_ttl_seconds == 0 {
+            anyhow::bail!("resume_ttl_seconds must be > 0, got: 0");
+        }
+
+        // Validate GreeterService-specific configuration
+        for (name, size) in [
+            (
+                "greeter.max_decoding_message_size",
+                self.greeter.max_decoding_message_size,
+            ),
+            (
+                "greeter.max_encoding_message_size",
+                self.greeter.max_encoding_message_size,
+            ),
+        ] {
+            if !(MIN_MESSAGE_SIZE_BYTES..=MAX_MESSAGE_SIZE_BYTES).contains(&size) {
+                anyhow::bail!(
+                    "{} must be between {} and {} bytes, got: {}",
+                    name,
+                    MIN_MESSAGE_SIZE_BYTES,
+                    MAX_MESSAGE_SIZE_BYTES,
+                    size
+                );
+            }
+        }
+
+        if self.greeter.max_concurrent_streams == 0 {
+            anyhow::bail!("greeter.max_concurrent_streams must be > 0, got: 0");
         }
 
-        if self.streaming.timeout_seconds == 0 {
-            anyhow::bail!("Timeout must be > 0 seconds, got: 0");
+        if self.greeter.default_interval_seconds == 0 {
+            anyhow::bail!("greeter.default_interval_seconds must be > 0, got: 0");
         }
 
-        if self.streaming.timeout_seconds > 86400 {
+        if self.greeter.default_interval_seconds > 3600 {
             anyhow::bail!(
-                "Timeout too large (max 86400s/24h), got: {}s",
-                self.streaming.timeout_seconds
+                "greeter.default_interval_seconds too large (max 3600s/1h), got: {}s",
+                self.greeter.default_interval_seconds
             );
         }
 
+        // Validate TLS configuration
+        if self.server.tls.enabled {
+            if self.server.tls.cert_path.as_deref().unwrap_or_default().is_empty() {
+                anyhow::bail!("server.tls.cert_path must be set when server.tls.enabled is true");
+            }
+            if self.server.tls.key_path.as_deref().unwrap_or_default().is_empty() {
+                anyhow::bail!("server.tls.key_path must be set when server.tls.enabled is true");
+            }
+
+            for (name, path) in [
+                ("server.tls.cert_path", self.server.tls.cert_path.as_deref()),
+                ("server.tls.key_path", self.server.tls.key_path.as_deref()),
+                (
+                    "server.tls.client_ca_path",
+                    self.server.tls.client_ca_path.as_deref(),
+                ),
+            ] {
+                if let Some(path) = path {
+                    std::fs::metadata(path)
+                        .map_err(|e| anyhow::anyhow!("{name} '{path}' is not readable: {e}"))?;
+                }
+            }
+        }
+
+        // Validate per-method serve-time budget overrides
+        for (method, budget_ms) in &self.budgets.overrides_ms {
+            if *budget_ms == 0 {
+                anyhow::bail!(
+                    "budgets.overrides_ms['{}'] must be > 0 when set, got: 0",
+                    method
+                );
+            }
+        }
+
         // Log level validation is now handled by the LogLevel enum
 
         Ok(())
     }
 }
 
+/// Process-global handle to the resolved [`AppConfig`], mirroring Rocket's
+/// `config::active()`/`get()`. Set once at startup via [`AppConfig::init_global`]
+/// (after [`AppConfig::validate`] passes) and re-published on every successful
+/// hot-reload by [`crate::config_watcher::ConfigWatcher`], so modules that only
+/// need an occasional read -- a log field, a threshold check -- can call
+/// [`AppConfig::global`] instead of receiving and storing their own clone.
+///
+/// Call sites that need a config fixed for their entire lifetime (the gRPC
+/// services, the test harness) should keep receiving `AppConfig` explicitly --
+/// this is for incidental reads, not a replacement for that wiring.
+///
+/// Assumes the `arc_swap` and `once_cell` crates are available.
+static GLOBAL_CONFIG: once_cell::sync::OnceCell<arc_swap::ArcSwap<AppConfig>> =
+    once_cell::sync::OnceCell::new();
+
+impl AppConfig {
+    /// Publishes `self` as the process-global config. Must be called exactly
+    /// once, after startup has loaded and validated the config. Returns an
+    /// error if the global has already been initialized.
+    pub fn init_global(self) -> anyhow::Result<()> {
+        GLOBAL_CONFIG
+            .set(arc_swap::ArcSwap::from_pointee(self))
+            .map_err(|_| anyhow::anyhow!("AppConfig::init_global called more than once"))
+    }
+
+    /// Returns a cheap, reference-counted handle to the current process-global
+    /// config.
+    ///
+    /// # Panics
+    /// Panics if called before [`AppConfig::init_global`] -- every binary
+    /// entry point is expected to initialize the global immediately after
+    /// validating its startup config.
+    pub fn global() -> std::sync::Arc<AppConfig> {
+        GLOBAL_CONFIG
+            .get()
+            .expect("AppConfig::global() called before AppConfig::init_global()")
+            .load_full()
+    }
+
+    /// Re-publishes `new` as the process-global config, e.g. after a
+    /// successful hot-reload. A no-op (other than logging nothing) if the
+    /// global was never initialized -- callers that hot-reload without ever
+    /// having called `init_global` simply don't participate in this facility.
+    pub fn set_global(new: AppConfig) {
+        if let Some(slot) = GLOBAL_CONFIG.get() {
+            slot.store(std::sync::Arc::new(new));
+        }
+    }
+}
+
+/// On-disk format for the config file, used both by `--dump-config` (printed
+/// to stdout) and `--init`/`--config-format` (written to the resolved config
+/// path), instead of being locked to whatever `config::File` auto-detects.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// File extension `config::File::with_name` will recognize this format by.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Json => "json",
+        }
+    }
+}
+
+/// Extensions `config::File::with_name` auto-detects, checked by
+/// [`config_file_exists`] when deciding whether `--init` should act.
+const KNOWN_CONFIG_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json", "ini"];
+
+/// Returns `true` if `base` (the same path passed to `--config` /
+/// `File::with_name`) already resolves to an existing file under any
+/// extension `config::File` auto-detects.
+pub fn config_file_exists(base: &str) -> bool {
+    KNOWN_CONFIG_EXTENSIONS
+        .iter()
+        .any(|ext| std::path::Path::new(&format!("{base}.{ext}")).exists())
+}
+
+/// Writes `AppConfig::default()`, serialized in `format`, to `{base}.{ext}`
+/// -- "a config has been created for you to modify" starting point for
+/// first-run users, rather than relying entirely on env vars. Returns the
+/// path written.
+pub fn init_config(base: &str, format: ConfigFormat) -> anyhow::Result<std::path::PathBuf> {
+    let default_config = AppConfig::default();
+
+    let contents = match format {
+        ConfigFormat::Toml => toml::to_string_pretty(&default_config)
+            .context("Failed to serialize default config as TOML")?,
+        // Assumes the `serde_yaml` crate is available alongside `toml`.
+        ConfigFormat::Yaml => serde_yaml::to_string(&default_config)
+            .context("Failed to serialize default config as YAML")?,
+        ConfigFormat::Json => serde_json::to_string_pretty(&default_config)
+            .context("Failed to serialize default config as JSON")?,
+    };
+
+    let path = std::path::PathBuf::from(format!("{base}.{}", format.extension()));
+    if let Some(parent) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory {parent:?}"))?;
+    }
+
+    // JSON has no comment syntax, so only TOML/YAML get the annotated header.
+    let header = match format {
+        ConfigFormat::Toml | ConfigFormat::Yaml => {
+            "# Generated default configuration -- edit as needed.\n"
+        }
+        ConfigFormat::Json => "",
+    };
+    std::fs::write(&path, format!("{header}{contents}"))
+        .with_context(|| format!("Failed to write default config to {path:?}"))?;
+
+    Ok(path)
+}
+
 /// Load configuration with layered approach:
 /// 1. Start with defaults
 /// 2. Override with config file (optional)
@@ -201,6 +731,49 @@ pub fn load_config() -> Result<AppConfig, ConfigError> {
     config.try_deserialize()
 }
 
+/// Load configuration with the same layered approach as [`load_config`], plus a
+/// final CLI layer on top (highest precedence):
+/// 1. Start with defaults
+/// 2. Override with config file (path from `cli.config`, optional)
+/// 3. Override with environment variables
+/// 4. Override with explicit CLI flags and `-v`/`-q` verbosity adjustments
+pub fn load_config_with_cli(cli: &crate::cli::Cli) -> Result<AppConfig, ConfigError> {
+    // Resolve the log level from defaults/file/env first, since `-v`/`-q` are
+    // defined as adjustments relative to whatever level those layers produced.
+    let pre_cli: AppConfig = Config::builder()
+        .add_source(Config::try_from(&AppConfig::default())?)
+        .add_source(File::with_name(&cli.config).required(false))
+        .add_source(
+            Environment::with_prefix("APP")
+                .separator("__")
+                .try_parsing(true),
+        )
+        .build()?
+        .try_deserialize()?;
+
+    let mut builder = Config::builder()
+        .add_source(Config::try_from(&AppConfig::default())?)
+        .add_source(File::with_name(&cli.config).required(false))
+        .add_source(
+            Environment::with_prefix("APP")
+                .separator("__")
+                .try_parsing(true),
+        )
+        .set_override(
+            "logging.level",
+            cli.adjust_log_level(&pre_cli.logging.level).to_string(),
+        )?;
+
+    if let Some(grpc_address) = &cli.grpc_address {
+        builder = builder.set_override("server.grpc_address", grpc_address.clone())?;
+    }
+    if let Some(log_format) = &cli.log_format {
+        builder = builder.set_override("logging.format", log_format.to_string())?;
+    }
+
+    builder.build()?.try_deserialize()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,15 +798,185 @@ mod tests {
     }
 
     #[test]
-    fn test_config_validation_health_port() {
+    fn test_port_rejects_privileged_values() {
+        assert!(Port::try_from(8081u16).is_ok());
+        assert!(Port::try_from(80u16).is_err());
+        assert!(Port::try_from(1024u16).is_ok());
+        assert!(Port::try_from(1023u16).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_shutdown_after_idle_seconds() {
+        let mut config = AppConfig::default();
+
+        // Unset (default) should pass
+        assert!(config.validate().is_ok());
+
+        // A positive value should pass
+        config.server.shutdown_after_idle_seconds = Some(30);
+        assert!(config.validate().is_ok());
+
+        // Zero should fail
+        config.server.shutdown_after_idle_seconds = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_message_size_limits() {
         let mut config = AppConfig::default();
 
-        // Valid port should pass
-        config.server.health_port = 8081;
+        // Unset (default) should pass
+        assert!(config.validate().is_ok());
+
+        // A reasonable value should pass
+        config.server.max_decoding_message_size = Some(4 * 1024 * 1024);
+        config.server.max_encoding_message_size = Some(4 * 1024 * 1024);
+        assert!(config.validate().is_ok());
+
+        // Too small should fail
+        config.server.max_decoding_message_size = Some(1);
+        assert!(config.validate().is_err());
+        config.server.max_decoding_message_size = Some(4 * 1024 * 1024);
+
+        // Too large should fail
+        config.server.max_encoding_message_size = Some(MAX_MESSAGE_SIZE_BYTES + 1);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_http2_window_sizes() {
+        let mut config = AppConfig::default();
+
+        // Unset (default) should pass
+        assert!(config.validate().is_ok());
+
+        // A reasonable value should pass
+        config.server.http2_initial_stream_window_size = Some(1024 * 1024);
+        config.server.http2_initial_connection_window_size = Some(1024 * 1024);
+        assert!(config.validate().is_ok());
+
+        // Zero should fail
+        config.server.http2_initial_stream_window_size = Some(0);
+        assert!(config.validate().is_err());
+        config.server.http2_initial_stream_window_size = Some(1024 * 1024);
+
+        // Above the HTTP/2 spec ceiling should fail
+        config.server.http2_initial_connection_window_size = Some(MAX_HTTP2_WINDOW_SIZE + 1);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_stalled_stream_protection() {
+        let mut config = AppConfig::default();
+
+        // Unset (default) should pass
+        assert!(config.validate().is_ok());
+
+        // A reasonable floor/grace pair should pass
+        config.streaming.stall_min_throughput_per_sec = Some(0.5);
+        config.streaming.stall_grace_period_seconds = Some(30);
+        assert!(config.validate().is_ok());
+
+        // Zero or negative floor should fail
+        config.streaming.stall_min_throughput_per_sec = Some(0.0);
+        assert!(config.validate().is_err());
+        config.streaming.stall_min_throughput_per_sec = Some(-1.0);
+        assert!(config.validate().is_err());
+        config.streaming.stall_min_throughput_per_sec = Some(0.5);
+
+        // Zero grace period should fail
+        config.streaming.stall_grace_period_seconds = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_resume_ttl() {
+        let mut config = AppConfig::default();
+
+        // Default resume TTL should pass
+        assert!(config.validate().is_ok());
+
+        // A reasonable value should pass
+        config.streaming.resume_ttl_seconds = 60;
         assert!(config.validate().is_ok());
 
-        // Invalid port should fail
-        config.server.health_port = 80;
+        // Zero should fail
+        config.streaming.resume_ttl_seconds = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_greeter_config() {
+        let mut config = AppConfig::default();
+
+        // Defaults should pass
+        assert!(config.validate().is_ok());
+
+        // Message sizes outside the shared bounds should fail
+        config.greeter.max_decoding_message_size = 1;
+        assert!(config.validate().is_err());
+        config.greeter.max_decoding_message_size = MAX_MESSAGE_SIZE_BYTES + 1;
+        assert!(config.validate().is_err());
+        config.greeter = GreeterConfig::default();
+
+        // Zero concurrent-stream cap should fail
+        config.greeter.max_concurrent_streams = 0;
+        assert!(config.validate().is_err());
+        config.greeter.max_concurrent_streams = 1000;
+
+        // Zero or overly large default interval should fail
+        config.greeter.default_interval_seconds = 0;
+        assert!(config.validate().is_err());
+        config.greeter.default_interval_seconds = 3601;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_tls_config() {
+        let mut config = AppConfig::default();
+
+        // Disabled (default) should pass even with no cert/key set
+        assert!(config.validate().is_ok());
+
+        // Enabled without cert_path/key_path should fail
+        config.server.tls.enabled = true;
+        assert!(config.validate().is_err());
+
+        let cert_file = tempfile::NamedTempFile::new().unwrap();
+        config.server.tls.cert_path = Some(cert_file.path().display().to_string());
+        assert!(config.validate().is_err());
+
+        // Enabled with both cert_path and key_path pointing at real, readable
+        // files should pass
+        let key_file = tempfile::NamedTempFile::new().unwrap();
+        config.server.tls.key_path = Some(key_file.path().display().to_string());
+        assert!(config.validate().is_ok());
+
+        // A cert_path/key_path that doesn't exist on disk should fail, even
+        // though it's non-empty
+        config.server.tls.cert_path = Some("/nonexistent/cert.pem".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_budget_overrides() {
+        let mut config = AppConfig::default();
+
+        // Unset (default) should pass
+        assert!(config.validate().is_ok());
+
+        // A positive override should pass
+        config
+            .budgets
+            .overrides_ms
+            .insert("SayHello".to_string(), 500);
+        assert!(config.validate().is_ok());
+
+        // Zero should fail
+        config
+            .budgets
+            .overrides_ms
+            .insert("SayHello".to_string(), 0);
         assert!(config.validate().is_err());
     }
 
@@ -380,64 +1123,24 @@ mod tests {
     }
 
     #[test]
-    fn test_streaming_config_validation() {
-        // Valid streaming config should pass
-        let mut config = AppConfig {
-            server: ServerConfig {
-                grpc_address: "127.0.0.1:50051".to_string(),
-                health_port: 8081,
-            },
-            logging: LoggingConfig {
-                level: LogLevel::Info,
-                format: LogFormat::Pretty,
-            },
-            streaming: StreamingConfig {
-                interval_seconds: 1,
-                max_connections: 100,
-                timeout_seconds: 300,
-            },
-        };
-        assert!(config.validate().is_ok());
-
-        // Test interval validation
-        config.streaming.interval_seconds = 0;
-        assert!(config.validate().is_err());
-
-        config.streaming.interval_seconds = 3601;
-        assert!(config.validate().is_err());
-
-        config.streaming.interval_seconds = 1; // Reset
-
-        // Test max_connections validation
-        config.streaming.max_connections = 0;
-        assert!(config.validate().is_err());
-
-        config.streaming.max_connections = 10001;
-        assert!(config.validate().is_err());
-
-        config.streaming.max_connections = 100; // Reset
-
-        // Test timeout validation
-        config.streaming.timeout_seconds = 0;
-        assert!(config.validate().is_err());
-
-        config.streaming.timeout_seconds = 86401;
-        assert!(config.validate().is_err());
-
-        // Edge cases that should be valid
-        config.streaming = StreamingConfig {
-            interval_seconds: 1, // minimum
-            max_connections: 1,  // minimum
-            timeout_seconds: 1,  // minimum
-        };
-        assert!(config.validate().is_ok());
-
-        config.streaming = StreamingConfig {
-            interval_seconds: 3600, // maximum
-            max_connections: 10000, // maximum
-            timeout_seconds: 86400, // maximum
-        };
-        assert!(config.validate().is_ok());
+    fn test_streaming_newtypes_reject_zero_and_out_of_bounds() {
+        // interval_seconds: 1s..=3600s
+        assert!(StreamingIntervalSeconds::try_from(0u64).is_err());
+        assert!(StreamingIntervalSeconds::try_from(3601u64).is_err());
+        assert!(StreamingIntervalSeconds::try_from(1u64).is_ok());
+        assert!(StreamingIntervalSeconds::try_from(3600u64).is_ok());
+
+        // max_connections: 1..=10000
+        assert!(MaxConnections::try_from(0u32).is_err());
+        assert!(MaxConnections::try_from(10_001u32).is_err());
+        assert!(MaxConnections::try_from(1u32).is_ok());
+        assert!(MaxConnections::try_from(10_000u32).is_ok());
+
+        // timeout_seconds: 1s..=86400s
+        assert!(StreamingTimeoutSeconds::try_from(0u64).is_err());
+        assert!(StreamingTimeoutSeconds::try_from(86_401u64).is_err());
+        assert!(StreamingTimeoutSeconds::try_from(1u64).is_ok());
+        assert!(StreamingTimeoutSeconds::try_from(86_400u64).is_ok());
     }
 
     #[test]
@@ -445,9 +1148,9 @@ mod tests {
         let config = AppConfig::default();
 
         // Verify default streaming values
-        assert_eq!(config.streaming.interval_seconds, 1);
-        assert_eq!(config.streaming.max_connections, 100);
-        assert_eq!(config.streaming.timeout_seconds, 300);
+        assert_eq!(config.streaming.interval_seconds.as_secs(), 1);
+        assert_eq!(config.streaming.max_connections.get(), 100);
+        assert_eq!(config.streaming.timeout_seconds.as_secs(), 300);
 
         // Defaults should be valid
         assert!(config.validate().is_ok());
@@ -511,7 +1214,8 @@ mod tests {
             "gRPC address not overridden"
         );
         assert_eq!(
-            config.server.health_port, 9090,
+            config.server.health_port.get(),
+            9090,
             "Health port not overridden"
         );
         assert_eq!(
@@ -527,15 +1231,18 @@ mod tests {
 
         // Test streaming configuration overrides
         assert_eq!(
-            config.streaming.interval_seconds, 5,
+            config.streaming.interval_seconds.as_secs(),
+            5,
             "Streaming interval not overridden"
         );
         assert_eq!(
-            config.streaming.max_connections, 200,
+            config.streaming.max_connections.get(),
+            200,
             "Streaming max connections not overridden"
         );
         assert_eq!(
-            config.streaming.timeout_seconds, 600,
+            config.streaming.timeout_seconds.as_secs(),
+            600,
             "Streaming timeout not overridden"
         );
 
@@ -0,0 +1,242 @@
+use std::path::PathBuf;
+use std::sync::mpsc as std_mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::cli::Cli;
+use crate::config::{load_config_with_cli, AppConfig};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Watches the config file backing `cli.config` (via `notify`) and republishes
+/// a freshly reloaded, re-validated [`AppConfig`] through a `tokio::sync::watch`
+/// channel whenever it changes on disk.
+///
+/// A reload that fails to load or fails [`AppConfig::validate`] never replaces
+/// the live config -- it's logged and retried with exponential backoff
+/// (doubling from 1s up to 60s), re-attempted on its own backoff timer even if
+/// no further fs event arrives, so a file left in a broken state mid-edit
+/// doesn't hang on the last-known-bad reload forever. Within
+/// [`crate::config::StreamingConfig`], only the live-safe fields are taken
+/// from the reloaded file and the rest keep their previous values; all other
+/// sections are published as reloaded. Anything already wired into a running
+/// `GreeterService` at construction time keeps its original value until
+/// restart. Callers that want the published config applied to a running
+/// server (rather than just observed) still need to subscribe and act on it --
+/// see `main.rs`'s `GreeterService::set_max_connections` wiring.
+pub struct ConfigWatcher {
+    receiver: watch::Receiver<AppConfig>,
+    _watcher: RecommendedWatcher,
+    _reload_task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Starts watching `cli.config`'s parent directory and spawns the reload
+    /// loop, seeded with `initial` (the config already loaded and validated
+    /// at startup, so subscribers never observe a gap before the first change).
+    pub fn spawn(cli: Cli, initial: AppConfig) -> Result<Self> {
+        let (tx, rx) = watch::channel(initial);
+        let (event_tx, event_rx) = std_mpsc::channel::<()>();
+
+        let config_path = PathBuf::from(&cli.config);
+        let watch_dir = config_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if event.is_ok() {
+                // The receiving end only cares that *something* changed, not what --
+                // the reload loop re-reads and re-validates from scratch either way.
+                let _ = event_tx.send(());
+            }
+        })
+        .context("Failed to create config file watcher")?;
+
+        watcher
+            .watch(&watch_dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch config directory {watch_dir:?}"))?;
+
+        let reload_task = tokio::task::spawn_blocking(move || {
+            Self::reload_loop(&cli, &tx, &event_rx);
+        });
+
+        Ok(Self {
+            receiver: rx,
+            _watcher: watcher,
+            _reload_task: reload_task,
+        })
+    }
+
+    /// Subscribe to live config updates. Each subscriber sees the most
+    /// recently published config immediately, then every subsequent change.
+    pub fn subscribe(&self) -> watch::Receiver<AppConfig> {
+        self.receiver.clone()
+    }
+
+    fn reload_loop(cli: &Cli, tx: &watch::Sender<AppConfig>, event_rx: &std_mpsc::Receiver<()>) {
+        let mut backoff = INITIAL_BACKOFF;
+        // Set once a reload fails validation, so the next wait is bounded by
+        // `backoff` instead of blocking indefinitely for the next fs event --
+        // a config left broken with no further saves still gets retried.
+        let mut last_reload_failed = false;
+
+        loop {
+            let wait_result = if last_reload_failed {
+                match event_rx.recv_timeout(backoff) {
+                    Ok(()) => Ok(()),
+                    Err(std_mpsc::RecvTimeoutError::Timeout) => Ok(()),
+                    Err(std_mpsc::RecvTimeoutError::Disconnected) => Err(()),
+                }
+            } else {
+                // Blocks until the watched directory reports a change, or the
+                // notify watcher (and its sender half) is dropped on shutdown.
+                event_rx.recv().map_err(|_| ())
+            };
+
+            if wait_result.is_err() {
+                break;
+            }
+
+            match load_config_with_cli(cli)
+                .map_err(anyhow::Error::from)
+                .and_then(|config| {
+                    config.validate()?;
+                    Ok(config)
+                }) {
+                Ok(mut reloaded) => {
+                    let mut streaming = tx.borrow().streaming.clone();
+                    streaming.apply_live_safe(&reloaded.streaming);
+                    reloaded.streaming = streaming;
+
+                    info!("Config file changed: reloaded and applied live-safe updates");
+                    AppConfig::set_global(reloaded.clone());
+                    let _ = tx.send(reloaded);
+                    backoff = INITIAL_BACKOFF;
+                    last_reload_failed = false;
+                }
+                Err(e) => {
+                    error!(
+                        error = %e,
+                        backoff_seconds = backoff.as_secs(),
+                        "Config reload failed validation, keeping last-good config"
+                    );
+                    last_reload_failed = true;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFormat;
+    use tempfile::TempDir;
+
+    fn test_cli(base: &std::path::Path) -> Cli {
+        Cli {
+            config: base.display().to_string(),
+            verbose: 0,
+            quiet: 0,
+            grpc_address: None,
+            log_format: None,
+            dump_config: None,
+            immediate_shutdown: false,
+            config_format: ConfigFormat::Toml,
+            init: false,
+        }
+    }
+
+    /// Writes `toml` to `{dir}/settings.toml` and returns the `--config` base
+    /// path (without extension) pointing at it.
+    fn write_config(dir: &TempDir, toml: &str) -> std::path::PathBuf {
+        std::fs::write(dir.path().join("settings.toml"), toml).unwrap();
+        dir.path().join("settings")
+    }
+
+    const VALID_CONFIG: &str =
+        "[streaming]\nmax_connections = 5\ninterval_seconds = 1\ntimeout_seconds = 30\nresume_ttl_seconds = 300\n";
+
+    #[tokio::test]
+    async fn test_reload_loop_applies_live_safe_update_on_fs_event() {
+        let dir = TempDir::new().unwrap();
+        let base = write_config(&dir, VALID_CONFIG);
+        let cli = test_cli(&base);
+
+        let initial = load_config_with_cli(&cli).unwrap();
+        let (tx, mut rx) = watch::channel(initial);
+        let (event_tx, event_rx) = std_mpsc::channel::<()>();
+
+        let reload_task = tokio::task::spawn_blocking(move || {
+            ConfigWatcher::reload_loop(&cli, &tx, &event_rx);
+        });
+
+        write_config(
+            &dir,
+            "[streaming]\nmax_connections = 9\ninterval_seconds = 1\ntimeout_seconds = 30\nresume_ttl_seconds = 300\n",
+        );
+        event_tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(2), rx.changed())
+            .await
+            .expect("expected a reload within 2s")
+            .unwrap();
+
+        assert_eq!(rx.borrow().streaming.max_connections.get(), 9);
+
+        drop(event_tx);
+        let _ = reload_task.await;
+    }
+
+    #[tokio::test]
+    async fn test_reload_loop_retries_failed_reload_without_a_new_fs_event() {
+        let dir = TempDir::new().unwrap();
+        let base = write_config(&dir, VALID_CONFIG);
+        let cli = test_cli(&base);
+
+        let initial = load_config_with_cli(&cli).unwrap();
+        let (tx, mut rx) = watch::channel(initial);
+        let (event_tx, event_rx) = std_mpsc::channel::<()>();
+
+        let reload_task = tokio::task::spawn_blocking(move || {
+            ConfigWatcher::reload_loop(&cli, &tx, &event_rx);
+        });
+
+        // Write a config that fails validation and tell the loop about it --
+        // this is the one and only fs event it ever sees in this test.
+        write_config(
+            &dir,
+            "[server]\ngrpc_address = \"not-an-address\"\n[streaming]\nmax_connections = 5\ninterval_seconds = 1\ntimeout_seconds = 30\nresume_ttl_seconds = 300\n",
+        );
+        event_tx.send(()).unwrap();
+
+        // Give the failed reload a moment to be observed, then fix the file on
+        // disk *without* sending another event -- the loop must self-retry on
+        // its own backoff timer rather than waiting forever for an fs event
+        // that never comes.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        write_config(
+            &dir,
+            "[streaming]\nmax_connections = 7\ninterval_seconds = 1\ntimeout_seconds = 30\nresume_ttl_seconds = 300\n",
+        );
+
+        tokio::time::timeout(Duration::from_secs(4), rx.changed())
+            .await
+            .expect("expected the loop to self-retry and pick up the fixed config without a new fs event")
+            .unwrap();
+
+        assert_eq!(rx.borrow().streaming.max_connections.get(), 7);
+
+        drop(event_tx);
+        let _ = reload_task.await;
+    }
+}
@@ -2,31 +2,190 @@
 ///
 /// Provides structured error types that map cleanly to gRPC status codes
 /// and include proper context for debugging and observability.
+use std::time::Duration;
 use tonic::Status;
-use tracing::warn;
+use tracing::{debug, error, warn};
+
+/// A single field-level violation, mirroring `google.rpc.BadRequest.FieldViolation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    pub field: String,
+    pub description: String,
+}
+
+/// Structured, machine-readable detail accumulated on an [`AppError`].
+///
+/// Encoded into the `grpc-status-details-bin` trailer as a `google.rpc.Status`
+/// (via the `tonic-types` crate) so clients can decode typed information --
+/// e.g. which field failed validation, or how long to back off before
+/// retrying -- instead of parsing the message prose. Named to avoid colliding
+/// with `tonic_types::ErrorDetails`, which this module converts to at the
+/// `Status` boundary.
+#[derive(Debug, Clone, Default)]
+pub struct AppErrorDetails {
+    pub field_violations: Vec<FieldViolation>,
+    /// Encoded as a `google.rpc.RetryInfo`, telling backpressure-aware
+    /// clients how long to wait before retrying. Typically set on
+    /// [`AppError::UnavailableError`] / [`AppError::ResourceExhausted`] via
+    /// [`AppError::retry_after`].
+    pub retry_after: Option<Duration>,
+}
+
+impl AppErrorDetails {
+    fn is_empty(&self) -> bool {
+        self.field_violations.is_empty() && self.retry_after.is_none()
+    }
+}
 
 /// Application-level errors that can occur during gRPC request processing
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     /// Domain validation errors (user input)
     #[error("Invalid input: {message}")]
-    ValidationError { message: String },
+    ValidationError {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
     /// Internal service errors (server-side)
     #[error("Internal service error: {message}")]
-    InternalError { message: String },
+    InternalError {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
     /// Configuration or startup errors
     #[error("Configuration error: {message}")]
-    ConfigurationError { message: String },
+    ConfigurationError {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
     /// Resource not found errors
     #[error("Resource not found: {message}")]
-    NotFoundError { message: String },
+    NotFoundError {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 
     /// Service temporarily unavailable
     #[error("Service unavailable: {message}")]
-    UnavailableError { message: String },
+    UnavailableError {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// A concurrency or rate limit was exceeded (e.g. max streaming connections)
+    #[error("Connection limit exceeded: {message}")]
+    ConnectionLimitExceeded {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// A request exceeded its allotted deadline/budget
+    #[error("Request timed out: {message}")]
+    Timeout {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The caller cancelled the request before it completed
+    #[error("Request cancelled: {message}")]
+    Cancelled {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The caller is authenticated but lacks permission for the operation
+    #[error("Permission denied: {message}")]
+    PermissionDenied {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The request lacks valid authentication credentials
+    #[error("Unauthenticated: {message}")]
+    Unauthenticated {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The resource the caller tried to create already exists
+    #[error("Already exists: {message}")]
+    AlreadyExists {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// A resource quota (distinct from [`AppError::ConnectionLimitExceeded`]'s
+    /// connection-count limit) was exhausted, e.g. a per-client rate limit
+    #[error("Resource exhausted: {message}")]
+    ResourceExhausted {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The system is not in a state required for the operation (and retrying
+    /// the exact same request won't help until that state changes)
+    #[error("Failed precondition: {message}")]
+    FailedPrecondition {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The operation was aborted, typically due to a concurrency conflict
+    /// (and, unlike `FailedPrecondition`, a retry of the same request may succeed)
+    #[error("Aborted: {message}")]
+    Aborted {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// The operation was attempted past the valid range of the data
+    #[error("Out of range: {message}")]
+    OutOfRange {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// Unrecoverable data loss or corruption was detected
+    #[error("Data loss: {message}")]
+    DataLoss {
+        message: String,
+        details: AppErrorDetails,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
 }
 
 impl AppError {
@@ -34,6 +193,8 @@ impl AppError {
     pub fn validation<S: Into<String>>(message: S) -> Self {
         AppError::ValidationError {
             message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
         }
     }
 
@@ -41,6 +202,8 @@ impl AppError {
     pub fn internal<S: Into<String>>(message: S) -> Self {
         AppError::InternalError {
             message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
         }
     }
 
@@ -48,6 +211,8 @@ impl AppError {
     pub fn configuration<S: Into<String>>(message: S) -> Self {
         AppError::ConfigurationError {
             message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
         }
     }
 
@@ -55,6 +220,8 @@ impl AppError {
     pub fn not_found<S: Into<String>>(message: S) -> Self {
         AppError::NotFoundError {
             message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
         }
     }
 
@@ -62,35 +229,404 @@ impl AppError {
     pub fn unavailable<S: Into<String>>(message: S) -> Self {
         AppError::UnavailableError {
             message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a connection-limit-exceeded error with context
+    pub fn connection_limit_exceeded<S: Into<String>>(message: S) -> Self {
+        AppError::ConnectionLimitExceeded {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a timeout error with context
+    pub fn timeout<S: Into<String>>(message: S) -> Self {
+        AppError::Timeout {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a cancellation error with context
+    pub fn cancelled<S: Into<String>>(message: S) -> Self {
+        AppError::Cancelled {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a permission-denied error with context
+    pub fn permission_denied<S: Into<String>>(message: S) -> Self {
+        AppError::PermissionDenied {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create an unauthenticated error with context
+    pub fn unauthenticated<S: Into<String>>(message: S) -> Self {
+        AppError::Unauthenticated {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create an already-exists error with context
+    pub fn already_exists<S: Into<String>>(message: S) -> Self {
+        AppError::AlreadyExists {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a resource-exhausted error with context
+    pub fn resource_exhausted<S: Into<String>>(message: S) -> Self {
+        AppError::ResourceExhausted {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a failed-precondition error with context
+    pub fn failed_precondition<S: Into<String>>(message: S) -> Self {
+        AppError::FailedPrecondition {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create an aborted error with context
+    pub fn aborted<S: Into<String>>(message: S) -> Self {
+        AppError::Aborted {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create an out-of-range error with context
+    pub fn out_of_range<S: Into<String>>(message: S) -> Self {
+        AppError::OutOfRange {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Create a data-loss error with context
+    pub fn data_loss<S: Into<String>>(message: S) -> Self {
+        AppError::DataLoss {
+            message: message.into(),
+            details: AppErrorDetails::default(),
+            source: None,
+        }
+    }
+
+    /// Returns a mutable reference to this error's accumulated details,
+    /// regardless of variant.
+    fn details_mut(&mut self) -> &mut AppErrorDetails {
+        match self {
+            AppError::ValidationError { details, .. }
+            | AppError::InternalError { details, .. }
+            | AppError::ConfigurationError { details, .. }
+            | AppError::NotFoundError { details, .. }
+            | AppError::UnavailableError { details, .. }
+            | AppError::ConnectionLimitExceeded { details, .. }
+            | AppError::Timeout { details, .. }
+            | AppError::Cancelled { details, .. }
+            | AppError::PermissionDenied { details, .. }
+            | AppError::Unauthenticated { details, .. }
+            | AppError::AlreadyExists { details, .. }
+            | AppError::ResourceExhausted { details, .. }
+            | AppError::FailedPrecondition { details, .. }
+            | AppError::Aborted { details, .. }
+            | AppError::OutOfRange { details, .. }
+            | AppError::DataLoss { details, .. } => details,
         }
     }
+
+    /// Accumulates a `google.rpc.BadRequest.FieldViolation`-style entry onto
+    /// this error, so a single `ValidationError` can report every invalid
+    /// field instead of just the first one found. Chainable:
+    /// `AppError::validation("...").with_field_violation("email", "must be valid")`.
+    pub fn with_field_violation(mut self, field: impl Into<String>, description: impl Into<String>) -> Self {
+        self.details_mut().field_violations.push(FieldViolation {
+            field: field.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// Sets a retry hint (encoded as a `google.rpc.RetryInfo` detail) telling
+    /// backpressure-aware clients how long to wait before retrying. Chainable:
+    /// `AppError::unavailable("...").retry_after(Duration::from_secs(2))`.
+    pub fn retry_after(mut self, duration: Duration) -> Self {
+        self.details_mut().retry_after = Some(duration);
+        self
+    }
+
+    /// Returns a mutable reference to this error's source slot, regardless of
+    /// variant.
+    fn source_mut(&mut self) -> &mut Option<Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            AppError::ValidationError { source, .. }
+            | AppError::InternalError { source, .. }
+            | AppError::ConfigurationError { source, .. }
+            | AppError::NotFoundError { source, .. }
+            | AppError::UnavailableError { source, .. }
+            | AppError::ConnectionLimitExceeded { source, .. }
+            | AppError::Timeout { source, .. }
+            | AppError::Cancelled { source, .. }
+            | AppError::PermissionDenied { source, .. }
+            | AppError::Unauthenticated { source, .. }
+            | AppError::AlreadyExists { source, .. }
+            | AppError::ResourceExhausted { source, .. }
+            | AppError::FailedPrecondition { source, .. }
+            | AppError::Aborted { source, .. }
+            | AppError::OutOfRange { source, .. }
+            | AppError::DataLoss { source, .. } => source,
+        }
+    }
+
+    /// Attaches `cause` as this error's source, so it's reachable via
+    /// `Error::source()`/[`AppError::chain()`] even though only `message`
+    /// ever reaches the client. Chainable, like [`AppError::with_field_violation`].
+    pub fn with_source<E>(mut self, cause: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        *self.source_mut() = Some(Box::new(cause));
+        self
+    }
+
+    /// Walks this error's causal chain: first `self`, then each `source()`
+    /// in turn, mirroring `anyhow::Error::chain()`.
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(Some(self as &(dyn std::error::Error + 'static)), |e| {
+            e.source()
+        })
+    }
+
+    /// True for errors caused by the caller's request itself (bad input,
+    /// missing auth, a resource that doesn't exist) -- the 4xx-equivalent
+    /// half of gRPC's status space. Mutually exclusive with
+    /// [`AppError::is_retryable`] and [`AppError::is_internal`].
+    pub fn is_client_error(&self) -> bool {
+        matches!(
+            self,
+            AppError::ValidationError { .. }
+                | AppError::NotFoundError { .. }
+                | AppError::PermissionDenied { .. }
+                | AppError::Unauthenticated { .. }
+                | AppError::AlreadyExists { .. }
+                | AppError::FailedPrecondition { .. }
+                | AppError::OutOfRange { .. }
+                | AppError::Cancelled { .. }
+        )
+    }
+
+    /// True for conditions a well-behaved client can reasonably retry,
+    /// typically with backoff (see [`AppError::retry_after`]).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            AppError::UnavailableError { .. }
+                | AppError::ConnectionLimitExceeded { .. }
+                | AppError::ResourceExhausted { .. }
+                | AppError::Aborted { .. }
+                | AppError::Timeout { .. }
+        )
+    }
+
+    /// True for server-side failures that are neither the caller's fault nor
+    /// retryable by them -- these get their message redacted before reaching
+    /// the client (see [`From<AppError> for Status`]) and are logged at
+    /// `error` level.
+    pub fn is_internal(&self) -> bool {
+        matches!(
+            self,
+            AppError::InternalError { .. } | AppError::ConfigurationError { .. } | AppError::DataLoss { .. }
+        )
+    }
+}
+
+/// Generic message returned to clients in place of an internal error's real
+/// message, which is logged in full server-side but never forwarded --
+/// internal messages can reference file paths, connection strings, or other
+/// details that shouldn't leak past the trailer.
+const REDACTED_INTERNAL_MESSAGE: &str = "An internal error occurred while processing the request";
+
+/// Wraps any `E: std::error::Error` into an `AppError::InternalError`,
+/// retaining `e` as the source (reachable via `AppError::chain()`) instead of
+/// flattening it into the message string the way [`ErrorContext`] does for
+/// non-`Error` types.
+pub trait IntoAppError<T> {
+    fn context(self, message: impl Into<String>) -> Result<T, AppError>;
+}
+
+impl<T, E> IntoAppError<T> for Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn context(self, message: impl Into<String>) -> Result<T, AppError> {
+        self.map_err(|e| AppError::internal(message.into()).with_source(e))
+    }
+}
+
+/// Classifies a `std::io::Error` by its `ErrorKind` into the closest-matching
+/// `AppError` variant, carrying the original error as the source. Lets
+/// handlers that touch files, sockets, or tokio IO use `?` directly and get a
+/// sensible gRPC status automatically, instead of hand-writing `map_err` at
+/// every call site.
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        let message = err.to_string();
+        match err.kind() {
+            std::io::ErrorKind::NotFound => AppError::not_found(message).with_source(err),
+            std::io::ErrorKind::PermissionDenied => {
+                AppError::permission_denied(message).with_source(err)
+            }
+            std::io::ErrorKind::ConnectionRefused
+            | std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::ConnectionAborted
+            | std::io::ErrorKind::NotConnected => {
+                AppError::unavailable(message).with_source(err)
+            }
+            std::io::ErrorKind::TimedOut => AppError::timeout(message).with_source(err),
+            _ => AppError::internal(message).with_source(err),
+        }
+    }
+}
+
+/// Builds a [`Status`] for `code`/`message`, encoding `details` as a
+/// `google.rpc.Status` into the `grpc-status-details-bin` trailer when
+/// non-empty so clients can decode structured information out of it.
+///
+/// Assumes the `tonic-types` crate is available alongside `tonic` itself.
+fn status_with_details(code: tonic::Code, message: String, details: &AppErrorDetails) -> Status {
+    if details.is_empty() {
+        return Status::new(code, message);
+    }
+
+    let mut rpc_details = tonic_types::ErrorDetails::new();
+    for violation in &details.field_violations {
+        rpc_details.add_bad_request_violation(violation.field.clone(), violation.description.clone());
+    }
+    if let Some(retry_after) = details.retry_after {
+        rpc_details.set_retry_info(Some(retry_after));
+    }
+
+    Status::with_error_details(code, message, rpc_details)
 }
 
 /// Convert AppError to appropriate gRPC Status codes
 impl From<AppError> for Status {
     fn from(error: AppError) -> Self {
-        match error {
-            AppError::ValidationError { message } => {
-                warn!(error_type = "validation", message = %message, "Request validation failed");
-                Status::invalid_argument(message)
+        // Log the full causal chain server-side (each `source()` link), but
+        // never forward it to the client -- only `message` (redacted for
+        // internal errors, below) ends up in the `Status`, so internals
+        // referenced by a wrapped `io::Error` or similar never leak past the
+        // trailer.
+        for (depth, cause) in error.chain().skip(1).enumerate() {
+            warn!(depth, cause = %cause, "error caused by");
+        }
+
+        // Centralizes log-severity policy: client-caused errors are expected
+        // traffic (`debug`), retryable conditions are worth a closer look
+        // (`warn`), and internal failures need attention (`error`).
+        let is_internal = error.is_internal();
+        let is_client_error = error.is_client_error();
+
+        let (error_type, code, mut client_message, details) = match error {
+            AppError::ValidationError { message, details, .. } => {
+                ("validation", tonic::Code::InvalidArgument, message, details)
+            }
+            AppError::InternalError { message, details, .. } => {
+                ("internal", tonic::Code::Internal, message, details)
+            }
+            AppError::ConfigurationError { message, details, .. } => (
+                "configuration",
+                tonic::Code::Internal,
+                format!("Service configuration error: {}", message),
+                details,
+            ),
+            AppError::NotFoundError { message, details, .. } => {
+                ("not_found", tonic::Code::NotFound, message, details)
+            }
+            AppError::UnavailableError { message, details, .. } => {
+                ("unavailable", tonic::Code::Unavailable, message, details)
+            }
+            AppError::ConnectionLimitExceeded { message, details, .. } => (
+                "connection_limit_exceeded",
+                tonic::Code::ResourceExhausted,
+                message,
+                details,
+            ),
+            AppError::Timeout { message, details, .. } => {
+                ("timeout", tonic::Code::DeadlineExceeded, message, details)
             }
-            AppError::InternalError { message } => {
-                warn!(error_type = "internal", message = %message, "Internal service error");
-                Status::internal(message)
+            AppError::Cancelled { message, details, .. } => {
+                ("cancelled", tonic::Code::Cancelled, message, details)
             }
-            AppError::ConfigurationError { message } => {
-                warn!(error_type = "configuration", message = %message, "Configuration error");
-                Status::internal(format!("Service configuration error: {}", message))
+            AppError::PermissionDenied { message, details, .. } => (
+                "permission_denied",
+                tonic::Code::PermissionDenied,
+                message,
+                details,
+            ),
+            AppError::Unauthenticated { message, details, .. } => (
+                "unauthenticated",
+                tonic::Code::Unauthenticated,
+                message,
+                details,
+            ),
+            AppError::AlreadyExists { message, details, .. } => {
+                ("already_exists", tonic::Code::AlreadyExists, message, details)
             }
-            AppError::NotFoundError { message } => {
-                warn!(error_type = "not_found", message = %message, "Resource not found");
-                Status::not_found(message)
+            AppError::ResourceExhausted { message, details, .. } => (
+                "resource_exhausted",
+                tonic::Code::ResourceExhausted,
+                message,
+                details,
+            ),
+            AppError::FailedPrecondition { message, details, .. } => (
+                "failed_precondition",
+                tonic::Code::FailedPrecondition,
+                message,
+                details,
+            ),
+            AppError::Aborted { message, details, .. } => {
+                ("aborted", tonic::Code::Aborted, message, details)
             }
-            AppError::UnavailableError { message } => {
-                warn!(error_type = "unavailable", message = %message, "Service unavailable");
-                Status::unavailable(message)
+            AppError::OutOfRange { message, details, .. } => {
+                ("out_of_range", tonic::Code::OutOfRange, message, details)
             }
+            AppError::DataLoss { message, details, .. } => {
+                ("data_loss", tonic::Code::DataLoss, message, details)
+            }
+        };
+
+        if is_internal {
+            error!(error_type, message = %client_message, "Internal error (message redacted from client)");
+            client_message = REDACTED_INTERNAL_MESSAGE.to_string();
+        } else if is_client_error {
+            debug!(error_type, message = %client_message, "Client-caused request failure");
+        } else {
+            warn!(error_type, message = %client_message, "Retryable request failure");
         }
+
+        status_with_details(code, client_message, &details)
     }
 }
 
@@ -133,6 +669,7 @@ pub type AppResult<T> = Result<T, AppError>;
 mod tests {
     use super::*;
     use tonic::Code;
+    use tonic_types::StatusExt;
 
     #[test]
     fn test_validation_error_to_status() {
@@ -149,7 +686,9 @@ mod tests {
         let status = Status::from(error);
 
         assert_eq!(status.code(), Code::Internal);
-        assert!(status.message().contains("Database connection failed"));
+        // Internal error messages are redacted before reaching the client.
+        assert!(!status.message().contains("Database connection failed"));
+        assert_eq!(status.message(), REDACTED_INTERNAL_MESSAGE);
     }
 
     #[test]
@@ -176,8 +715,114 @@ mod tests {
         let status = Status::from(error);
 
         assert_eq!(status.code(), Code::Internal);
-        assert!(status.message().contains("Service configuration error"));
-        assert!(status.message().contains("Invalid config setting"));
+        // Configuration errors are internal and get redacted before reaching the client.
+        assert!(!status.message().contains("Invalid config setting"));
+        assert_eq!(status.message(), REDACTED_INTERNAL_MESSAGE);
+    }
+
+    #[test]
+    fn test_connection_limit_exceeded_to_status() {
+        let error = AppError::connection_limit_exceeded("Max streaming connections reached");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert!(status
+            .message()
+            .contains("Max streaming connections reached"));
+    }
+
+    #[test]
+    fn test_timeout_to_status() {
+        let error = AppError::timeout("Handler exceeded its serve-time budget");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+        assert!(status
+            .message()
+            .contains("Handler exceeded its serve-time budget"));
+    }
+
+    #[test]
+    fn test_cancelled_to_status() {
+        let error = AppError::cancelled("Client hung up");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::Cancelled);
+        assert!(status.message().contains("Client hung up"));
+    }
+
+    #[test]
+    fn test_permission_denied_to_status() {
+        let error = AppError::permission_denied("Caller lacks the admin role");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::PermissionDenied);
+        assert!(status.message().contains("Caller lacks the admin role"));
+    }
+
+    #[test]
+    fn test_unauthenticated_to_status() {
+        let error = AppError::unauthenticated("Missing bearer token");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::Unauthenticated);
+        assert!(status.message().contains("Missing bearer token"));
+    }
+
+    #[test]
+    fn test_already_exists_to_status() {
+        let error = AppError::already_exists("User already registered");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::AlreadyExists);
+        assert!(status.message().contains("User already registered"));
+    }
+
+    #[test]
+    fn test_resource_exhausted_to_status() {
+        let error = AppError::resource_exhausted("Per-client quota exceeded");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert!(status.message().contains("Per-client quota exceeded"));
+    }
+
+    #[test]
+    fn test_failed_precondition_to_status() {
+        let error = AppError::failed_precondition("Account must be verified first");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert!(status.message().contains("Account must be verified first"));
+    }
+
+    #[test]
+    fn test_aborted_to_status() {
+        let error = AppError::aborted("Concurrent update conflict");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::Aborted);
+        assert!(status.message().contains("Concurrent update conflict"));
+    }
+
+    #[test]
+    fn test_out_of_range_to_status() {
+        let error = AppError::out_of_range("Page offset beyond result set");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::OutOfRange);
+        assert!(status.message().contains("Page offset beyond result set"));
+    }
+
+    #[test]
+    fn test_data_loss_to_status() {
+        let error = AppError::data_loss("Checksum mismatch on read");
+        let status = Status::from(error);
+
+        assert_eq!(status.code(), Code::DataLoss);
+        // Data loss is internal and gets redacted before reaching the client.
+        assert!(!status.message().contains("Checksum mismatch on read"));
+        assert_eq!(status.message(), REDACTED_INTERNAL_MESSAGE);
     }
 
     #[test]
@@ -203,4 +848,211 @@ mod tests {
         assert!(error.to_string().contains("User input invalid"));
         assert!(error.to_string().contains("validation failed"));
     }
+
+    #[test]
+    fn test_field_violation_round_trips_through_status_details() {
+        let error = AppError::validation("request failed validation")
+            .with_field_violation("email", "must be a valid email address")
+            .with_field_violation("age", "must be non-negative");
+        let status = Status::from(error);
+
+        let rpc_details = status.get_error_details();
+        let bad_request = rpc_details
+            .bad_request()
+            .expect("expected a BadRequest detail to be present");
+
+        assert_eq!(bad_request.field_violations.len(), 2);
+        assert_eq!(bad_request.field_violations[0].field, "email");
+        assert_eq!(
+            bad_request.field_violations[0].description,
+            "must be a valid email address"
+        );
+        assert_eq!(bad_request.field_violations[1].field, "age");
+    }
+
+    #[test]
+    fn test_no_field_violations_means_no_details_payload() {
+        let error = AppError::validation("plain validation error");
+        let status = Status::from(error);
+
+        let rpc_details = status.get_error_details();
+        assert!(rpc_details.bad_request().is_none());
+    }
+
+    #[test]
+    fn test_with_source_is_reachable_via_std_error_source() {
+        use std::error::Error as _;
+
+        let cause = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+        let error = AppError::internal("Failed to write file").with_source(cause);
+
+        let source = error.source().expect("source should be set");
+        assert_eq!(source.to_string(), "disk full");
+    }
+
+    #[test]
+    fn test_chain_walks_every_cause() {
+        let root = std::io::Error::new(std::io::ErrorKind::Other, "root cause");
+        let error = AppError::internal("Mid-level failure").with_source(root);
+
+        let chain: Vec<_> = error.chain().map(|e| e.to_string()).collect();
+        assert_eq!(chain, vec!["Internal service error: Mid-level failure", "root cause"]);
+    }
+
+    #[test]
+    fn test_context_combinator_wraps_std_error_as_source() {
+        let result: Result<i32, std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing file"));
+        let app_result = result.context("Failed to read config");
+
+        assert!(app_result.is_err());
+        let error = app_result.unwrap_err();
+        assert!(matches!(error, AppError::InternalError { .. }));
+        assert_eq!(error.chain().count(), 2);
+        assert!(error.to_string().contains("Failed to read config"));
+    }
+
+    #[test]
+    fn test_io_error_not_found_maps_to_not_found_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
+    #[test]
+    fn test_io_error_permission_denied_maps_to_permission_denied_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "no access");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn test_io_error_connection_refused_maps_to_unavailable_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+
+    #[test]
+    fn test_io_error_connection_reset_maps_to_unavailable_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+
+    #[test]
+    fn test_io_error_connection_aborted_maps_to_unavailable_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::ConnectionAborted, "aborted");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+
+    #[test]
+    fn test_io_error_not_connected_maps_to_unavailable_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotConnected, "not connected");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+
+    #[test]
+    fn test_io_error_timed_out_maps_to_deadline_exceeded_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+    }
+
+    #[test]
+    fn test_io_error_other_kind_maps_to_internal_status() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::Other, "something broke");
+        let status = Status::from(AppError::from(io_err));
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[test]
+    fn test_io_error_source_is_preserved() {
+        use std::error::Error as _;
+
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
+        let app_err = AppError::from(io_err);
+
+        assert!(app_err.source().is_some());
+    }
+
+    #[test]
+    fn test_retry_after_round_trips_through_status_details() {
+        let error = AppError::unavailable("Overloaded, back off")
+            .retry_after(Duration::from_secs(5));
+        let status = Status::from(error);
+
+        let rpc_details = status.get_error_details();
+        let retry_info = rpc_details
+            .retry_info()
+            .expect("expected a RetryInfo detail to be present");
+
+        assert_eq!(retry_info.retry_delay, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_no_retry_after_means_no_retry_info_payload() {
+        let error = AppError::unavailable("plain unavailable error");
+        let status = Status::from(error);
+
+        let rpc_details = status.get_error_details();
+        assert!(rpc_details.retry_info().is_none());
+    }
+
+    #[test]
+    fn test_classification_is_mutually_exclusive_and_exhaustive() {
+        let all_errors = [
+            AppError::validation("x"),
+            AppError::internal("x"),
+            AppError::configuration("x"),
+            AppError::not_found("x"),
+            AppError::unavailable("x"),
+            AppError::connection_limit_exceeded("x"),
+            AppError::timeout("x"),
+            AppError::cancelled("x"),
+            AppError::permission_denied("x"),
+            AppError::unauthenticated("x"),
+            AppError::already_exists("x"),
+            AppError::resource_exhausted("x"),
+            AppError::failed_precondition("x"),
+            AppError::aborted("x"),
+            AppError::out_of_range("x"),
+            AppError::data_loss("x"),
+        ];
+
+        for error in &all_errors {
+            let flags = [
+                error.is_client_error(),
+                error.is_retryable(),
+                error.is_internal(),
+            ];
+            assert_eq!(
+                flags.iter().filter(|&&f| f).count(),
+                1,
+                "expected exactly one classification flag for {error:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_client_error_for_validation() {
+        assert!(AppError::validation("x").is_client_error());
+        assert!(!AppError::validation("x").is_retryable());
+        assert!(!AppError::validation("x").is_internal());
+    }
+
+    #[test]
+    fn test_is_retryable_for_unavailable() {
+        assert!(AppError::unavailable("x").is_retryable());
+        assert!(!AppError::unavailable("x").is_client_error());
+    }
+
+    #[test]
+    fn test_is_internal_for_internal_error() {
+        assert!(AppError::internal("x").is_internal());
+        assert!(!AppError::internal("x").is_client_error());
+        assert!(!AppError::internal("x").is_retryable());
+    }
 }
@@ -1,6 +1,10 @@
 // Library exports and module structure
+pub mod budget;
+pub mod cli;
 pub mod config;
+pub mod config_watcher;
 pub mod error;
+pub mod proxy_protocol;
 pub mod utils;
 
 pub mod services {
@@ -254,6 +258,72 @@ impl TimeSnapshot {
     }
 }
 
+/// Maximum wall-clock correction `Timestamper` applies per tick in `Skew` mode
+const MAX_SKEW_CORRECTION_MS: i64 = 5;
+
+/// Controls how a [`Timestamper`] derives each streamed `TimeSnapshot`
+///
+/// A backward system-clock step (e.g. an NTP correction) can make raw
+/// `Utc::now()` reads go non-monotonic mid-stream; these modes trade off
+/// simplicity against protection from that, mirroring RTP-style timestamping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampingMode {
+    /// Read `Utc::now()` on every tick (current default behavior)
+    #[default]
+    Wallclock,
+    /// Anchor once at `(Instant::now(), Utc::now())` and derive every timestamp
+    /// as `anchor_wall + elapsed_monotonic`, ignoring later wall-clock changes
+    Monotonic,
+    /// Like `Monotonic`, but slews the projection toward the real wall clock by
+    /// at most `MAX_SKEW_CORRECTION_MS` per tick, so it stays monotonic and
+    /// smooth while still converging on true time
+    Skew,
+}
+
+/// Stateful generator that derives successive `TimeSnapshot`s according to a [`TimestampingMode`]
+#[derive(Debug, Clone)]
+pub struct Timestamper {
+    mode: TimestampingMode,
+    anchor_instant: std::time::Instant,
+    anchor_wall: DateTime<Utc>,
+    /// Total correction applied so far; only ever moves in `Skew` mode
+    correction: chrono::Duration,
+}
+
+impl Timestamper {
+    /// Creates a new timestamper, anchoring to the current instant and wall-clock time
+    pub fn new(mode: TimestampingMode) -> Self {
+        Self {
+            mode,
+            anchor_instant: std::time::Instant::now(),
+            anchor_wall: Utc::now(),
+            correction: chrono::Duration::zero(),
+        }
+    }
+
+    /// Produces the next timestamp in the sequence, per the configured mode
+    pub fn next(&mut self) -> TimeSnapshot {
+        match self.mode {
+            TimestampingMode::Wallclock => TimeSnapshot::now(),
+            TimestampingMode::Monotonic => TimeSnapshot::from_datetime(self.projected_time()),
+            TimestampingMode::Skew => {
+                let projected = self.projected_time();
+                let error = Utc::now() - projected;
+                let max_step = chrono::Duration::milliseconds(MAX_SKEW_CORRECTION_MS);
+                let step = error.clamp(-max_step, max_step);
+                self.correction += step;
+                TimeSnapshot::from_datetime(projected + step)
+            }
+        }
+    }
+
+    /// Pure monotonic projection: `anchor_wall + elapsed + correction`
+    fn projected_time(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.anchor_instant.elapsed()).unwrap_or_default();
+        self.anchor_wall + elapsed + self.correction
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,4 +538,45 @@ mod tests {
         assert_eq!(snapshot1, snapshot2);
         assert_ne!(snapshot1, snapshot3);
     }
+
+    // Timestamper tests
+
+    #[test]
+    fn test_timestamper_wallclock_matches_now() {
+        let mut timestamper = Timestamper::new(TimestampingMode::Wallclock);
+        let before = TimeSnapshot::now().timestamp();
+        let snapshot = timestamper.next();
+        let after = TimeSnapshot::now().timestamp();
+
+        assert!(snapshot.timestamp() >= before && snapshot.timestamp() <= after);
+    }
+
+    #[test]
+    fn test_timestamper_monotonic_is_non_decreasing() {
+        let mut timestamper = Timestamper::new(TimestampingMode::Monotonic);
+        let mut previous = timestamper.next().as_datetime().to_owned();
+
+        for _ in 0..5 {
+            let current = timestamper.next().as_datetime().to_owned();
+            assert!(current >= previous);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn test_timestamper_skew_is_non_decreasing() {
+        let mut timestamper = Timestamper::new(TimestampingMode::Skew);
+        let mut previous = timestamper.next().as_datetime().to_owned();
+
+        for _ in 0..5 {
+            let current = timestamper.next().as_datetime().to_owned();
+            assert!(current >= previous);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn test_timestamping_mode_default_is_wallclock() {
+        assert_eq!(TimestampingMode::default(), TimestampingMode::Wallclock);
+    }
 }
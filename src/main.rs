@@ -1,45 +1,138 @@
 use anyhow::{Context, Result};
-use std::time::Duration;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
 use tokio::signal;
-use tonic::transport::Server;
+use tokio::sync::Notify;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
 use tonic_health::server::health_reporter;
 use tonic_reflection::server::Builder as ReflectionBuilder;
 use tracing::{info, warn};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use hello_world_grpc::config::{load_config, LogFormat, LoggingConfig};
-use hello_world_grpc::services::hello_world::{greeter_server::GreeterServer, GreeterService};
-use hello_world_grpc::utils::{start_health_server, SimpleMetrics};
+use clap::Parser;
+use hello_world_grpc::budget::MethodBudgets;
+use hello_world_grpc::cli::Cli;
+use hello_world_grpc::config::{
+    config_file_exists, init_config, load_config_with_cli, ConfigFormat, LogFormat, LoggingConfig,
+};
+use hello_world_grpc::services::hello_world::{
+    greeter_server::GreeterServer, GreeterService, GREETER_SERVICE_NAME,
+};
+use hello_world_grpc::utils::tracing_interceptor::RequestTracingLayer;
+use hello_world_grpc::utils::{start_health_server, ServiceHealthStatus, SimpleMetrics};
+use hello_world_grpc::TimestampingMode;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Load and validate configuration early
-    let config = load_config().context("Failed to load configuration")?;
+    // Parse CLI flags first, since --init only needs them (not a loaded config).
+    let cli = Cli::parse();
+
+    // First-run convenience: if the resolved config file doesn't exist yet and
+    // --init was given, write an annotated default and exit, so users get a
+    // discoverable starting point instead of relying entirely on env vars.
+    if cli.init && !config_file_exists(&cli.config) {
+        let written = init_config(&cli.config, cli.config_format)
+            .context("Failed to write default config file")?;
+        println!(
+            "A default configuration file has been created for you to modify: {}",
+            written.display()
+        );
+        return Ok(());
+    }
+
+    let config = load_config_with_cli(&cli).context("Failed to load configuration")?;
 
     config
         .validate()
         .context("Configuration validation failed")?;
 
+    // Print the fully-resolved config and exit, without starting anything --
+    // lets operators and integration tests verify the effective config directly.
+    if let Some(format) = &cli.dump_config {
+        let dumped = match format {
+            ConfigFormat::Json => serde_json::to_string_pretty(&config)
+                .context("Failed to serialize configuration as JSON")?,
+            // Assumes the `toml` and `serde_yaml` crates are available alongside
+            // `config`'s other format backends.
+            ConfigFormat::Toml => {
+                toml::to_string_pretty(&config).context("Failed to serialize configuration as TOML")?
+            }
+            ConfigFormat::Yaml => serde_yaml::to_string(&config)
+                .context("Failed to serialize configuration as YAML")?,
+        };
+        println!("{dumped}");
+        return Ok(());
+    }
+
     // Initialize logging with config
     init_logging(&config.logging)?;
 
     info!(
         grpc_address = %config.server.grpc_address,
-        health_port = config.server.health_port,
+        health_port = config.server.health_port.get(),
         log_level = %config.logging.level,
         log_format = %config.logging.format,
-        streaming_interval_seconds = config.streaming.interval_seconds,
-        streaming_max_connections = config.streaming.max_connections,
-        streaming_timeout_seconds = config.streaming.timeout_seconds,
+        streaming_interval_seconds = config.streaming.interval_seconds.as_secs(),
+        streaming_max_connections = config.streaming.max_connections.get(),
+        streaming_timeout_seconds = config.streaming.timeout_seconds.as_secs(),
+        stall_min_throughput_per_sec = ?config.streaming.stall_min_throughput_per_sec,
+        stall_grace_period_seconds = ?config.streaming.stall_grace_period_seconds,
+        resume_ttl_seconds = config.streaming.resume_ttl_seconds,
+        shutdown_after_idle_seconds = ?config.server.shutdown_after_idle_seconds,
+        budget_overrides_ms = ?config.budgets.overrides_ms,
+        greeter_max_concurrent_streams = config.greeter.max_concurrent_streams,
+        greeter_default_interval_seconds = config.greeter.default_interval_seconds,
         version = env!("CARGO_PKG_VERSION"),
         "Starting Hello World gRPC Server with configuration"
     );
 
+    // Publish the resolved config as the process-global handle so incidental
+    // readers (a log field, a threshold check) can call `AppConfig::global()`
+    // instead of receiving and storing their own clone.
+    config
+        .clone()
+        .init_global()
+        .context("Failed to publish global configuration")?;
+
+    // Watch the config file for changes and keep a live-safe-updated copy
+    // available for any component that wants to subscribe to it. Each
+    // reload also re-publishes the global handle above.
+    let config_watcher =
+        hello_world_grpc::config_watcher::ConfigWatcher::spawn(cli.clone(), config.clone())
+            .context("Failed to start config file watcher")?;
+    let mut live_config = config_watcher.subscribe();
+
     // Create metrics collection instance
     let metrics = SimpleMetrics::new();
 
+    // Seed per-method serve-time budgets from config, refined at runtime from observed durations
+    let method_budgets = std::sync::Arc::new(MethodBudgets::new(&config.budgets.overrides_ms));
+
     // Create the gRPC service instance with metrics
-    let greeter_service = GreeterService::new(metrics.clone());
+    let greeter_service = GreeterService::new(
+        metrics.clone(),
+        config.streaming.max_connections.get(),
+        config.streaming.stall_min_throughput_per_sec,
+        config
+            .streaming
+            .stall_grace_period_seconds
+            .map(Duration::from_secs),
+        TimestampingMode::Wallclock,
+        method_budgets,
+        Duration::from_secs(config.streaming.resume_ttl_seconds),
+        config.greeter.clone(),
+    );
+
+    // Apply the config watcher's live-safe `streaming.max_connections` updates
+    // to the running service, instead of only sizing admission once at startup.
+    let greeter_service_for_config = greeter_service.clone();
+    tokio::spawn(async move {
+        while live_config.changed().await.is_ok() {
+            let max_connections = live_config.borrow().streaming.max_connections.get();
+            greeter_service_for_config.set_max_connections(max_connections);
+        }
+    });
 
     // Setup gRPC health check service
     let (health_reporter, health_service) = health_reporter();
@@ -47,13 +140,19 @@ async fn main() -> Result<()> {
         .set_serving::<GreeterServer<GreeterService>>()
         .await;
 
+    // Mirrored by the HTTP /health endpoint so it stays consistent with the gRPC
+    // health service without polling it
+    let service_health = std::sync::Arc::new(ServiceHealthStatus::new());
+    service_health.set_serving(GREETER_SERVICE_NAME);
+    let service_health_for_http = service_health.clone();
+
     // Parse server address from configuration
     let addr = config
         .server
         .grpc_address
         .parse()
         .context("Failed to parse gRPC address")?;
-    let health_port = config.server.health_port;
+    let health_port = config.server.health_port.get();
 
     info!(address = %addr, "gRPC server will listen on");
     info!(
@@ -61,6 +160,23 @@ async fn main() -> Result<()> {
         "HTTP health check server will start on port"
     );
 
+    let listener = TcpListener::bind(addr)
+        .await
+        .context("Failed to bind gRPC listener")?;
+
+    if config.server.proxy_protocol_trusted_upstreams.is_empty() {
+        info!("PROXY protocol support disabled (no trusted upstreams configured)");
+    } else {
+        info!(
+            trusted_upstreams = ?config.server.proxy_protocol_trusted_upstreams,
+            "PROXY protocol support enabled for configured upstreams"
+        );
+    }
+    let incoming = hello_world_grpc::proxy_protocol::incoming(
+        listener,
+        config.server.proxy_protocol_trusted_upstreams.clone(),
+    );
+
     // Start periodic metrics logging task
     let metrics_clone = metrics.clone();
     tokio::spawn(async move {
@@ -74,9 +190,64 @@ async fn main() -> Result<()> {
 
     info!("Started periodic metrics logging (every 60 seconds)");
 
+    // Idle-shutdown supervisor: fires the same graceful-shutdown path as
+    // SIGTERM/Ctrl+C once there have been no active streams for the configured
+    // duration. Lets the server run on-demand and exit cleanly once clients disconnect.
+    //
+    // Always spawned (rather than only when `shutdown_after_idle_seconds` is set
+    // at startup) so the threshold stays live-reconfigurable: it's read fresh from
+    // `AppConfig::global()` on every poll tick instead of being captured once here.
+    // `None` just means the idle check never trips.
+    let idle_notify = std::sync::Arc::new(Notify::new());
+    {
+        let metrics_for_idle = metrics.clone();
+        let idle_notify_for_supervisor = idle_notify.clone();
+
+        tokio::spawn(async move {
+            let mut idle_since: Option<Instant> = None;
+            let mut poll_interval = tokio::time::interval(Duration::from_secs(1));
+
+            loop {
+                poll_interval.tick().await;
+
+                let idle_threshold = hello_world_grpc::config::AppConfig::global()
+                    .server
+                    .shutdown_after_idle_seconds
+                    .map(Duration::from_secs);
+
+                let Some(idle_threshold) = idle_threshold else {
+                    idle_since = None;
+                    continue;
+                };
+
+                let active = metrics_for_idle.active_streams.load(Ordering::Relaxed);
+                if active == 0 {
+                    let since = *idle_since.get_or_insert_with(Instant::now);
+                    let elapsed = since.elapsed();
+
+                    if elapsed >= idle_threshold {
+                        info!(
+                            idle_seconds = elapsed.as_secs(),
+                            "No active streams for idle threshold, triggering graceful shutdown"
+                        );
+                        idle_notify_for_supervisor.notify_one();
+                        break;
+                    }
+                } else {
+                    idle_since = None;
+                }
+            }
+        });
+
+        info!("Idle-shutdown supervisor armed (threshold tracks AppConfig::global() live)");
+    }
+
     // Start HTTP health check server
+    let metrics_for_health = metrics.clone();
     tokio::spawn(async move {
-        if let Err(e) = start_health_server(health_port).await {
+        if let Err(e) =
+            start_health_server(health_port, metrics_for_health, service_health_for_http).await
+        {
             tracing::error!(error = %e, "Failed to start health server");
         }
     });
@@ -93,14 +264,85 @@ async fn main() -> Result<()> {
 
     info!("gRPC reflection service enabled for service discovery");
 
+    // Apply message-size limits to the generated service: `server.max_*_message_size`
+    // overrides `greeter.max_*_message_size` when explicitly set, so the service
+    // always has a usable ceiling even without touching `ServerConfig`.
+    let max_decoding_message_size = config
+        .server
+        .max_decoding_message_size
+        .unwrap_or(config.greeter.max_decoding_message_size);
+    let max_encoding_message_size = config
+        .server
+        .max_encoding_message_size
+        .unwrap_or(config.greeter.max_encoding_message_size);
+
+    let greeter_server = GreeterServer::new(greeter_service)
+        .max_decoding_message_size(max_decoding_message_size)
+        .max_encoding_message_size(max_encoding_message_size);
+
+    info!(
+        max_decoding_message_size,
+        max_encoding_message_size,
+        http2_initial_stream_window_size = ?config.server.http2_initial_stream_window_size,
+        http2_initial_connection_window_size = ?config.server.http2_initial_connection_window_size,
+        "Effective gRPC transport limits"
+    );
+
     // Build and start the gRPC server with graceful shutdown handling
-    let server = Server::builder()
+    let mut server_builder = Server::builder()
+        .http2_initial_stream_window_size(config.server.http2_initial_stream_window_size)
+        .http2_initial_connection_window_size(config.server.http2_initial_connection_window_size);
+
+    if config.server.tls.enabled {
+        let cert_path = config
+            .server
+            .tls
+            .cert_path
+            .as_deref()
+            .context("server.tls.enabled is true but cert_path is not set")?;
+        let key_path = config
+            .server
+            .tls
+            .key_path
+            .as_deref()
+            .context("server.tls.enabled is true but key_path is not set")?;
+
+        let cert_pem = std::fs::read_to_string(cert_path)
+            .with_context(|| format!("Failed to read TLS certificate at {cert_path}"))?;
+        let key_pem = std::fs::read_to_string(key_path)
+            .with_context(|| format!("Failed to read TLS key at {key_path}"))?;
+
+        let mut tls_config = ServerTlsConfig::new().identity(Identity::from_pem(cert_pem, key_pem));
+
+        if let Some(ca_path) = &config.server.tls.client_ca_path {
+            let ca_pem = std::fs::read_to_string(ca_path)
+                .with_context(|| format!("Failed to read client CA certificate at {ca_path}"))?;
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(ca_pem));
+            info!("mTLS enabled for gRPC server (client certificates required)");
+        } else {
+            info!("TLS enabled for gRPC server");
+        }
+
+        server_builder = server_builder
+            .tls_config(tls_config)
+            .context("Failed to configure server TLS")?;
+    } else {
+        info!("TLS disabled for gRPC server (plaintext)");
+    }
+
+    let server = server_builder
+        .layer(RequestTracingLayer)
         .add_service(health_service)
         .add_service(reflection_service)
-        .add_service(GreeterServer::new(greeter_service));
+        .add_service(greeter_server);
 
     info!("Starting gRPC server with graceful shutdown support");
 
+    if cli.immediate_shutdown {
+        info!("--immediate-shutdown set: startup succeeded, returning before the serve loop");
+        return Ok(());
+    }
+
     // Create graceful shutdown signal handler
     let shutdown_signal = async {
         // Handle different shutdown signals across platforms
@@ -132,6 +374,9 @@ async fn main() -> Result<()> {
             _ = sigint => {
                 info!("Received Ctrl+C, initiating graceful shutdown");
             },
+            _ = idle_notify.notified() => {
+                info!("Idle-shutdown threshold reached, initiating graceful shutdown");
+            },
         }
     };
 
@@ -139,7 +384,7 @@ async fn main() -> Result<()> {
     let shutdown_timeout = Duration::from_secs(30); // Give 30 seconds for graceful shutdown
 
     // Wrap shutdown logic with timeout to prevent hanging indefinitely
-    let server_task = server.serve_with_shutdown(addr, shutdown_signal);
+    let server_task = server.serve_with_incoming_shutdown(incoming, shutdown_signal);
 
     info!(
         timeout_seconds = shutdown_timeout.as_secs(),
@@ -0,0 +1,444 @@
+//! PROXY protocol (v1 text and v2 binary) support for recovering the real
+//! client address behind an L4 load balancer.
+//!
+//! [`try_parse`] decodes the header itself; [`ProxyProtocolStream`] sniffs it
+//! off the front of a raw `TcpStream` at accept time (only for peers listed in
+//! `server.proxy_protocol_trusted_upstreams`, so an untrusted client can't
+//! spoof it) and exposes the decoded source address to request handlers via
+//! `Connected::connect_info()`, for [`crate::utils::extract_client_info`] to
+//! prefer over the socket peer address.
+
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::StreamExt;
+use tonic::transport::server::Connected;
+
+/// Binary signature that opens every PROXY protocol v2 header
+const V2_SIGNATURE: [u8; 12] = [
+    0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54, 0x0A,
+];
+
+/// Maximum bytes to buffer while sniffing for a PROXY protocol preamble.
+/// Covers a v2 header with the largest address block (16-byte fixed part plus
+/// 216 bytes of AF_UNIX addresses) without giving a client room to stall the
+/// acceptor. A longer header (e.g. one carrying TLVs) is not recognized, and
+/// the bytes read are passed through as ordinary connection data.
+const MAX_PREAMBLE_BYTES: usize = 256;
+
+/// The piece of a decoded PROXY protocol header callers care about: the
+/// original client's address, as seen by the proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProxyHeader {
+    pub source_addr: SocketAddr,
+}
+
+/// Attempts to parse a PROXY protocol v1 (text) header from the start of `buf`.
+///
+/// Recognizes `PROXY TCP4 <src> <dst> <sport> <dport>\r\n` and the `TCP6`
+/// variant; `PROXY UNKNOWN\r\n` parses successfully but carries no address.
+/// Returns `None` if `buf` doesn't yet contain a complete `\r\n`-terminated
+/// line, or the line isn't a valid v1 header.
+fn parse_v1(buf: &[u8]) -> Option<(Option<ProxyHeader>, usize)> {
+    let line_end = buf.windows(2).position(|w| w == b"\r\n")?;
+    let line = std::str::from_utf8(&buf[..line_end]).ok()?;
+    let consumed = line_end + 2;
+
+    let mut parts = line.split(' ');
+    if parts.next()? != "PROXY" {
+        return None;
+    }
+
+    match parts.next()? {
+        "UNKNOWN" => Some((None, consumed)),
+        "TCP4" | "TCP6" => {
+            let src_ip: IpAddr = parts.next()?.parse().ok()?;
+            let _dst_ip: IpAddr = parts.next()?.parse().ok()?;
+            let src_port: u16 = parts.next()?.parse().ok()?;
+            let _dst_port: u16 = parts.next()?.parse().ok()?;
+
+            Some((
+                Some(ProxyHeader {
+                    source_addr: SocketAddr::new(src_ip, src_port),
+                }),
+                consumed,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Attempts to parse a PROXY protocol v2 (binary) header from the start of `buf`.
+///
+/// Only the `PROXY` command over `AF_INET`/`AF_INET6` is decoded into a source
+/// address; `LOCAL` connections (the proxy's own health checks) parse
+/// successfully with no address. Expects `buf` to start with the v2 signature
+/// (checked by [`try_parse`], not here). Returns `None` if the version nibble
+/// isn't 2, or `buf` doesn't yet hold a complete header.
+fn parse_v2(buf: &[u8]) -> Option<(Option<ProxyHeader>, usize)> {
+    if buf.len() < 16 {
+        return None;
+    }
+
+    let version_command = buf[12];
+    if version_command >> 4 != 2 {
+        return None;
+    }
+    let command = version_command & 0x0F;
+
+    let address_family = buf[13] >> 4;
+    let addr_len = u16::from_be_bytes([buf[14], buf[15]]) as usize;
+    let header_len = 16 + addr_len;
+
+    if buf.len() < header_len {
+        return None;
+    }
+
+    // Command 0 is LOCAL (the proxy checking itself); only command 1 (PROXY)
+    // carries a real client address.
+    if command != 1 {
+        return Some((None, header_len));
+    }
+
+    let addr_bytes = &buf[16..header_len];
+    let source_addr = match address_family {
+        // AF_INET: 4-byte src, 4-byte dst, 2-byte src port, 2-byte dst port
+        1 if addr_bytes.len() >= 12 => {
+            let ip = IpAddr::from([addr_bytes[0], addr_bytes[1], addr_bytes[2], addr_bytes[3]]);
+            let port = u16::from_be_bytes([addr_bytes[8], addr_bytes[9]]);
+            Some(SocketAddr::new(ip, port))
+        }
+        // AF_INET6: 16-byte src, 16-byte dst, 2-byte src port, 2-byte dst port
+        2 if addr_bytes.len() >= 36 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&addr_bytes[0..16]);
+            let port = u16::from_be_bytes([addr_bytes[32], addr_bytes[33]]);
+            Some(SocketAddr::new(IpAddr::from(octets), port))
+        }
+        _ => None,
+    };
+
+    Some((
+        source_addr.map(|source_addr| ProxyHeader { source_addr }),
+        header_len,
+    ))
+}
+
+/// Attempts to parse a PROXY protocol header (v2 binary, checked first, or v1
+/// text) from the leading bytes of a connection.
+///
+/// Returns `Some((header, bytes_consumed))` once a complete header has been
+/// seen -- `header` is `None` for a valid header that carries no address
+/// (`UNKNOWN`/`LOCAL`). Returns `None` if `buf` doesn't start with a
+/// recognized signature, or doesn't yet hold a complete header -- callers
+/// sniffing a live connection should read more and retry.
+pub fn try_parse(buf: &[u8]) -> Option<(Option<ProxyHeader>, usize)> {
+    if buf.len() >= 12 && buf[..12] == V2_SIGNATURE {
+        return parse_v2(buf);
+    }
+    if buf.starts_with(b"PROXY ") {
+        return parse_v1(buf);
+    }
+    None
+}
+
+/// Per-connection info exposed to request handlers for a connection accepted
+/// through [`incoming`], in place of tonic's built-in `TcpConnectInfo`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProxyProtocolConnectInfo {
+    /// The direct TCP peer -- the load balancer, if one is in front of us.
+    pub peer_addr: SocketAddr,
+    /// The original client address decoded from a trusted PROXY protocol
+    /// header, if one was present.
+    pub proxy_source_addr: Option<SocketAddr>,
+}
+
+/// Wraps a raw connection, having already sniffed and consumed an optional
+/// leading PROXY protocol header from it, and stands in for it transparently
+/// for the rest of the connection's lifetime.
+pub struct ProxyProtocolStream<S> {
+    inner: S,
+    /// Bytes already read from `inner` past the PROXY header (or, if no
+    /// header was present/trusted, everything read while sniffing for one),
+    /// not yet returned to the caller via `poll_read`.
+    leftover: Vec<u8>,
+    leftover_pos: usize,
+    peer_addr: SocketAddr,
+    proxy_source_addr: Option<SocketAddr>,
+}
+
+impl ProxyProtocolStream<TcpStream> {
+    /// Accepts a raw TCP connection, sniffing a PROXY protocol header off its
+    /// leading bytes only when `peer_addr` is in `trusted_upstreams` --
+    /// connections from anywhere else are passed through untouched so a
+    /// client can't spoof `client_addr` by sending a fake header itself.
+    async fn accept(
+        mut inner: TcpStream,
+        peer_addr: SocketAddr,
+        trusted_upstreams: &[IpAddr],
+    ) -> io::Result<Self> {
+        if !trusted_upstreams.contains(&peer_addr.ip()) {
+            return Ok(Self {
+                inner,
+                leftover: Vec::new(),
+                leftover_pos: 0,
+                peer_addr,
+                proxy_source_addr: None,
+            });
+        }
+
+        let mut buf = Vec::with_capacity(64);
+        let mut chunk = [0u8; 64];
+
+        loop {
+            let n = inner.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+
+            if let Some((header, consumed)) = try_parse(&buf) {
+                return Ok(Self {
+                    inner,
+                    leftover: buf[consumed..].to_vec(),
+                    leftover_pos: 0,
+                    peer_addr,
+                    proxy_source_addr: header.map(|h| h.source_addr),
+                });
+            }
+
+            if buf.len() >= MAX_PREAMBLE_BYTES {
+                break;
+            }
+        }
+
+        // No valid header arrived within the sniffing budget; treat
+        // everything read so far as ordinary connection data.
+        Ok(Self {
+            inner,
+            leftover: buf,
+            leftover_pos: 0,
+            peer_addr,
+            proxy_source_addr: None,
+        })
+    }
+}
+
+impl<S> Connected for ProxyProtocolStream<S> {
+    type ConnectInfo = ProxyProtocolConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        ProxyProtocolConnectInfo {
+            peer_addr: self.peer_addr,
+            proxy_source_addr: self.proxy_source_addr,
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for ProxyProtocolStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if this.leftover_pos < this.leftover.len() {
+            let remaining = &this.leftover[this.leftover_pos..];
+            let n = remaining.len().min(buf.remaining());
+            buf.put_slice(&remaining[..n]);
+            this.leftover_pos += n;
+            return Poll::Ready(Ok(()));
+        }
+
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for ProxyProtocolStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Wraps a bound listener's accepted connections with PROXY protocol
+/// sniffing, for use with `Server::serve_with_incoming[_shutdown]` in place
+/// of `Server::serve`.
+pub fn incoming(
+    listener: TcpListener,
+    trusted_upstreams: Vec<IpAddr>,
+) -> impl Stream<Item = io::Result<ProxyProtocolStream<TcpStream>>> {
+    TcpListenerStream::new(listener).then(move |conn| {
+        let trusted_upstreams = trusted_upstreams.clone();
+        async move {
+            let stream = conn?;
+            let peer_addr = stream.peer_addr()?;
+            ProxyProtocolStream::accept(stream, peer_addr, &trusted_upstreams).await
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_parse_v1_tcp4() {
+        let input = b"PROXY TCP4 192.168.1.1 192.168.1.2 56324 443\r\nGET / HTTP/1.1\r\n";
+        let (header, consumed) = try_parse(input).unwrap();
+        let header = header.unwrap();
+
+        assert_eq!(header.source_addr, "192.168.1.1:56324".parse().unwrap());
+        assert_eq!(&input[consumed..], b"GET / HTTP/1.1\r\n");
+    }
+
+    #[test]
+    fn test_parse_v1_tcp6() {
+        let input = b"PROXY TCP6 ::1 ::1 56324 443\r\n";
+        let (header, consumed) = try_parse(input).unwrap();
+        let header = header.unwrap();
+
+        assert_eq!(header.source_addr, "[::1]:56324".parse().unwrap());
+        assert_eq!(consumed, input.len());
+    }
+
+    #[test]
+    fn test_parse_v1_unknown_has_no_address() {
+        let input = b"PROXY UNKNOWN\r\n";
+        let (header, consumed) = try_parse(input).unwrap();
+
+        assert!(header.is_none());
+        assert_eq!(consumed, input.len());
+    }
+
+    #[test]
+    fn test_parse_v1_incomplete_line_returns_none() {
+        let input = b"PROXY TCP4 192.168.1.1";
+        assert!(try_parse(input).is_none());
+    }
+
+    #[test]
+    fn test_parse_v2_ipv4() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.push(0x21); // version 2, command PROXY
+        input.push(0x11); // AF_INET, STREAM
+        input.extend_from_slice(&12u16.to_be_bytes());
+        input.extend_from_slice(&[10, 0, 0, 1]); // src addr
+        input.extend_from_slice(&[10, 0, 0, 2]); // dst addr
+        input.extend_from_slice(&51216u16.to_be_bytes()); // src port
+        input.extend_from_slice(&443u16.to_be_bytes()); // dst port
+        input.extend_from_slice(b"trailing payload");
+
+        let (header, consumed) = try_parse(&input).unwrap();
+        let header = header.unwrap();
+
+        assert_eq!(header.source_addr, "10.0.0.1:51216".parse().unwrap());
+        assert_eq!(&input[consumed..], b"trailing payload");
+    }
+
+    #[test]
+    fn test_parse_v2_local_has_no_address() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.push(0x20); // version 2, command LOCAL
+        input.push(0x00); // AF_UNSPEC
+        input.extend_from_slice(&0u16.to_be_bytes());
+
+        let (header, consumed) = try_parse(&input).unwrap();
+
+        assert!(header.is_none());
+        assert_eq!(consumed, input.len());
+    }
+
+    #[test]
+    fn test_parse_v2_incomplete_header_returns_none() {
+        let mut input = V2_SIGNATURE.to_vec();
+        input.push(0x21);
+        input.push(0x11);
+        input.extend_from_slice(&12u16.to_be_bytes());
+        // Declares 12 address bytes but only provides 4
+        input.extend_from_slice(&[10, 0, 0, 1]);
+
+        assert!(try_parse(&input).is_none());
+    }
+
+    #[test]
+    fn test_try_parse_rejects_unrecognized_input() {
+        assert!(try_parse(b"GET / HTTP/1.1\r\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_accept_passes_through_untrusted_peer_unmodified() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 2222\r\nhello")
+                .await
+                .unwrap();
+        });
+
+        let (conn, peer_addr) = listener.accept().await.unwrap();
+        let mut wrapped = ProxyProtocolStream::accept(conn, peer_addr, &[]).await.unwrap();
+        client.await.unwrap();
+
+        let mut buf = [0u8; 128];
+        let n = wrapped.read(&mut buf).await.unwrap();
+
+        assert!(buf[..n].starts_with(b"PROXY TCP4"));
+        assert_eq!(
+            wrapped.connect_info().proxy_source_addr,
+            None,
+            "header must be ignored for an untrusted peer"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accept_decodes_header_from_trusted_peer() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 2222\r\nhello")
+                .await
+                .unwrap();
+        });
+
+        let (conn, peer_addr) = listener.accept().await.unwrap();
+        let mut wrapped = ProxyProtocolStream::accept(conn, peer_addr, &[peer_addr.ip()])
+            .await
+            .unwrap();
+        client.await.unwrap();
+
+        let mut buf = [0u8; 128];
+        let n = wrapped.read(&mut buf).await.unwrap();
+
+        assert_eq!(&buf[..n], b"hello");
+        assert_eq!(
+            wrapped.connect_info().proxy_source_addr,
+            Some("1.2.3.4:1111".parse().unwrap())
+        );
+    }
+}
@@ -1,19 +1,36 @@
+use dashmap::DashMap;
 use futures::Stream;
+use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tokio::time;
 use tokio_stream::{wrappers::IntervalStream, StreamExt};
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::error::ErrorContext;
-use crate::utils::{extract_client_info, RequestTimer, SimpleMetrics};
-use crate::{GreetingMessage, PersonName, StreamInterval, TimeSnapshot};
-
-// Include the generated protobuf types
+use crate::budget::MethodBudgets;
+use crate::config::GreeterConfig;
+use crate::error::{AppError, ErrorContext};
+use crate::utils::{extract_client_info, parse_grpc_timeout_header, RequestTimer, SimpleMetrics};
+use crate::{
+    GreetingMessage, PersonName, StreamInterval, TimeSnapshot, Timestamper, TimestampingMode,
+};
+
+// Include the generated protobuf types. `StreamGreetings` assumes hello_world.proto
+// has been extended with `rpc StreamGreetings(stream HelloRequest) returns (stream HelloReply)`;
+// resumable, client-tunable `StreamTime` assumes `TimeRequest` gained `resume_token`,
+// `interval_millis`, `max_messages`, and `max_duration_millis` fields (0 meaning
+// "use the default"/"unbounded"), and `TimeResponse` gained `sequence`/`stream_id` fields.
 tonic::include_proto!("hello_world");
 
+/// Fully-qualified gRPC service name, as registered with the health service
+/// and used in `HealthCheckRequest::service`
+pub const GREETER_SERVICE_NAME: &str = "hello_world.Greeter";
+
 /// gRPC service implementation for the Hello World Greeter service
 ///
 /// Provides domain-validated greeting functionality with structured logging
@@ -21,22 +38,394 @@ tonic::include_proto!("hello_world");
 #[derive(Debug, Clone)]
 pub struct GreeterService {
     metrics: Arc<SimpleMetrics>,
+    /// Live `streaming.max_connections` cap, checked against `current_connections`
+    /// on each `StreamTime` admission. Stored as an atomic rather than baked into
+    /// a fixed-size `Semaphore` so [`GreeterService::set_max_connections`] can
+    /// apply a reloaded config to the running server, not just future instances.
+    max_connections: Arc<AtomicU64>,
+    /// Count of currently admitted `StreamTime` subscriptions
+    current_connections: Arc<AtomicU64>,
+    /// Minimum sustained throughput (messages/sec) a time stream must keep draining;
+    /// `None` disables stalled-stream protection.
+    stall_min_throughput_per_sec: Option<f64>,
+    /// How long throughput may stay below the floor before the stream is aborted
+    stall_grace_period: Option<Duration>,
+    /// How `StreamTime` derives each emitted timestamp
+    timestamping_mode: TimestampingMode,
+    /// Per-method serve-time budgets, enforced as a fail-fast deadline on each RPC
+    method_budgets: Arc<MethodBudgets>,
+    /// Resume state for `StreamTime` subscriptions, keyed by stream UUID: the next
+    /// sequence number to emit and when the stream was last seen. Entries older
+    /// than the configured TTL are evicted by a background sweeper.
+    stream_states: Arc<DashMap<Uuid, StreamState>>,
+    /// Message-size ceilings, the service-wide concurrent-stream cap, and the
+    /// default `StreamTime` interval
+    greeter_config: GreeterConfig,
 }
 
 impl GreeterService {
-    /// Create a new GreeterService with metrics collection
-    pub fn new(metrics: Arc<SimpleMetrics>) -> Self {
-        Self { metrics }
+    /// Create a new GreeterService with metrics collection, a streaming connection cap,
+    /// optional stalled-stream protection, a timestamping mode, per-method serve-time
+    /// budgets, and a resume-token TTL for `StreamTime`
+    ///
+    /// `max_connections` bounds how many `StreamTime` subscriptions may be open at
+    /// once; requests beyond that are rejected with `RESOURCE_EXHAUSTED`.
+    ///
+    /// `stall_min_throughput_per_sec` and `stall_grace_period` are either both `Some`
+    /// (a `StreamTime` subscriber whose effective throughput stays below the floor for
+    /// longer than the grace period is aborted) or both `None` (protection disabled).
+    ///
+    /// `resume_ttl` bounds how long a disconnected stream's resume state is kept
+    /// around; a `resume_token` presented after that window mints a fresh stream
+    /// rather than continuing the old one. A background task sweeps expired entries.
+    ///
+    /// `greeter_config` carries message-size ceilings, the service-wide
+    /// concurrent-stream cap (checked against `metrics.active_streams` in
+    /// `stream_time`), and the default `StreamTime` interval.
+    pub fn new(
+        metrics: Arc<SimpleMetrics>,
+        max_connections: u32,
+        stall_min_throughput_per_sec: Option<f64>,
+        stall_grace_period: Option<Duration>,
+        timestamping_mode: TimestampingMode,
+        method_budgets: Arc<MethodBudgets>,
+        resume_ttl: Duration,
+        greeter_config: GreeterConfig,
+    ) -> Self {
+        let stream_states: Arc<DashMap<Uuid, StreamState>> = Arc::new(DashMap::new());
+        spawn_resume_state_sweeper(stream_states.clone(), resume_ttl);
+
+        Self {
+            metrics,
+            max_connections: Arc::new(AtomicU64::new(max_connections as u64)),
+            current_connections: Arc::new(AtomicU64::new(0)),
+            stall_min_throughput_per_sec,
+            stall_grace_period,
+            timestamping_mode,
+            method_budgets,
+            stream_states,
+            greeter_config,
+        }
+    }
+
+    /// Live-update the `streaming.max_connections` cap checked by `StreamTime`
+    /// admission, e.g. from a [`crate::config_watcher::ConfigWatcher`]
+    /// subscriber reacting to a reloaded config. Already-admitted streams are
+    /// unaffected; only future admission checks see the new cap.
+    pub fn set_max_connections(&self, max_connections: u32) {
+        self.max_connections
+            .store(max_connections as u64, Ordering::Relaxed);
     }
+
+    /// Attempts to admit one more concurrent `StreamTime` subscription against
+    /// the live cap set by [`GreeterService::set_max_connections`]. Returns
+    /// `None` if the cap is currently full.
+    fn try_acquire_connection_permit(&self) -> Option<ConnectionPermit> {
+        let max_connections = self.max_connections.load(Ordering::Relaxed);
+
+        loop {
+            let current = self.current_connections.load(Ordering::Acquire);
+            if current >= max_connections {
+                return None;
+            }
+
+            if self
+                .current_connections
+                .compare_exchange_weak(current, current + 1, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return Some(ConnectionPermit {
+                    current_connections: self.current_connections.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// RAII guard held for the lifetime of an admitted `StreamTime` subscription;
+/// frees its slot against the live `streaming.max_connections` cap on drop
+struct ConnectionPermit {
+    current_connections: Arc<AtomicU64>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.current_connections.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Resume state for a single `StreamTime` subscription
+#[derive(Debug)]
+struct StreamState {
+    /// Sequence number the next emitted `TimeResponse` should carry
+    next_sequence: u64,
+    /// When this stream was last seen emitting a message or being resumed
+    last_active: Instant,
+    /// Set while a live `StreamTime` call is actively resuming or running this
+    /// entry, and cleared by [`ResumeClaimGuard`]'s `Drop` if setup fails or is
+    /// cancelled, or by [`StreamGuard`]'s `Drop` once a successfully started
+    /// stream ends. Lets [`GreeterService::resolve_stream_resumption`]
+    /// atomically refuse a second, concurrent resume of the same
+    /// `resume_token` instead of letting both claim it and race to overwrite
+    /// `next_sequence`.
+    claimed: bool,
+}
+
+/// RAII guard for the resume-token claim [`GreeterService::resolve_stream_resumption`]
+/// takes on an existing entry.
+///
+/// Releases the claim (`state.claimed = false`) on drop unless [`Self::disarm`]
+/// was called first. This covers every way `StreamTime` setup can end before
+/// a [`StreamGuard`] takes over: the `max_concurrent_streams`/`max_connections`
+/// admission checks and interval validation in `setup_time_stream` returning
+/// `Err`, or the serve-time budget timeout in `stream_time` abandoning the
+/// whole setup future. Without it, a resume attempt that merely loses a race
+/// against one of those would permanently poison its token -- every later
+/// resume would find `claimed == true` forever and silently mint a fresh
+/// stream instead, until the TTL sweeper evicts the entry wholesale.
+struct ResumeClaimGuard {
+    stream_states: Arc<DashMap<Uuid, StreamState>>,
+    stream_id: Uuid,
+    armed: bool,
+}
+
+impl ResumeClaimGuard {
+    fn new(stream_states: Arc<DashMap<Uuid, StreamState>>, stream_id: Uuid) -> Self {
+        Self {
+            stream_states,
+            stream_id,
+            armed: true,
+        }
+    }
+
+    /// Hands the claim off to the stream that's about to start running;
+    /// the guard no longer releases it on drop.
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for ResumeClaimGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            if let Some(mut state) = self.stream_states.get_mut(&self.stream_id) {
+                state.claimed = false;
+            }
+        }
+    }
+}
+
+/// Background task that evicts resume-state entries that have gone quiet for
+/// longer than `ttl`, so [`GreeterService::stream_states`] cannot grow unbounded
+/// from streams that disconnect and never reconnect.
+fn spawn_resume_state_sweeper(stream_states: Arc<DashMap<Uuid, StreamState>>, ttl: Duration) {
+    let sweep_interval = ttl.max(Duration::from_secs(1));
+
+    tokio::spawn(async move {
+        let mut interval = time::interval(sweep_interval);
+        loop {
+            interval.tick().await;
+            stream_states.retain(|_, state| state.last_active.elapsed() < ttl);
+        }
+    });
 }
 
 // Type alias for the time streaming response stream
 type TimeStream = Pin<Box<dyn Stream<Item = Result<TimeResponse, Status>> + Send + 'static>>;
 
+/// RAII guard for the lifetime of a single `StreamTime` subscription
+///
+/// Records `streams_started`/`active_streams` on construction and reliably
+/// decrements `active_streams` in `Drop`, regardless of how the stream ends
+/// (client disconnect, server shutdown, or cancellation) -- tonic drops the
+/// boxed `TimeStream` in all of those cases, which drops this guard along
+/// with it. Also logs a `stream ended` event with the total message count
+/// and connection duration.
+struct StreamGuard {
+    metrics: Arc<SimpleMetrics>,
+    stream_id: Uuid,
+    started_at: Instant,
+    messages_sent: Arc<AtomicU64>,
+    stream_states: Arc<DashMap<Uuid, StreamState>>,
+    /// Held for the stream's lifetime; releases the `max_connections` slot on drop
+    _connection_permit: ConnectionPermit,
+}
+
+impl StreamGuard {
+    fn new(
+        metrics: Arc<SimpleMetrics>,
+        stream_id: Uuid,
+        messages_sent: Arc<AtomicU64>,
+        stream_states: Arc<DashMap<Uuid, StreamState>>,
+        connection_permit: ConnectionPermit,
+    ) -> Self {
+        metrics.record_stream_started();
+
+        Self {
+            metrics,
+            stream_id,
+            started_at: Instant::now(),
+            messages_sent,
+            stream_states,
+            _connection_permit: connection_permit,
+        }
+    }
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        self.metrics.record_stream_completed();
+
+        // Release the resume claim so a later reconnect with this stream's
+        // token can resume it again; the entry itself stays put for the resume
+        // sweeper to evict once `last_active` goes stale.
+        if let Some(mut state) = self.stream_states.get_mut(&self.stream_id) {
+            state.claimed = false;
+        }
+
+        info!(
+            stream_id = %self.stream_id,
+            total_messages = self.messages_sent.load(Ordering::Relaxed),
+            duration_ms = self.started_at.elapsed().as_millis() as u64,
+            "stream ended"
+        );
+    }
+}
+
+/// Rolling window over which [`StalledStreamGuard`] measures throughput
+const STALL_WINDOW: Duration = Duration::from_secs(10);
+
+/// Wraps a `TimeStream` with minimum-throughput protection
+///
+/// Tracks a sliding window of successfully produced messages; when it is
+/// polled with nothing ready from the inner stream and its 1-second recheck
+/// tick has fired, it re-checks the throughput over that window against a
+/// configured floor. Below-floor time accumulates toward the grace period
+/// whenever the inner stream has nothing ready, and each produced message
+/// resets it to zero. The guard does not distinguish a producer with nothing
+/// to send from a client that is not consuming.
+struct StalledStreamGuard {
+    inner: TimeStream,
+    stream_id: Uuid,
+    client_addr: String,
+    floor_per_sec: f64,
+    grace_period: Duration,
+    window: VecDeque<Instant>,
+    stalled_for: Duration,
+    last_check: Instant,
+    recheck_interval: time::Interval,
+    aborted: bool,
+}
+
+impl StalledStreamGuard {
+    fn new(
+        inner: TimeStream,
+        stream_id: Uuid,
+        client_addr: String,
+        floor_per_sec: f64,
+        grace_period: Duration,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            inner,
+            stream_id,
+            client_addr,
+            floor_per_sec,
+            grace_period,
+            window: VecDeque::new(),
+            stalled_for: Duration::ZERO,
+            last_check: now,
+            recheck_interval: time::interval(Duration::from_secs(1)),
+            aborted: false,
+        }
+    }
+
+    fn prune_window(&mut self, now: Instant) {
+        while let Some(&front) = self.window.front() {
+            if now.duration_since(front) > STALL_WINDOW {
+                self.window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+impl Stream for StalledStreamGuard {
+    type Item = Result<TimeResponse, Status>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.aborted {
+            return Poll::Ready(None);
+        }
+
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                let now = Instant::now();
+                this.window.push_back(now);
+                this.prune_window(now);
+                this.stalled_for = Duration::ZERO;
+                this.last_check = now;
+                return Poll::Ready(Some(item));
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => {
+                // Producer has nothing ready yet; fall through to the periodic
+                // re-check, which counts this time toward the grace period if
+                // throughput is below the floor.
+            }
+        }
+
+        if this.recheck_interval.poll_tick(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let now = Instant::now();
+        this.prune_window(now);
+        let elapsed_since_check = now.duration_since(this.last_check);
+        this.last_check = now;
+
+        let throughput = this.window.len() as f64 / STALL_WINDOW.as_secs_f64();
+
+        if throughput < this.floor_per_sec {
+            this.stalled_for += elapsed_since_check;
+
+            if this.stalled_for >= this.grace_period {
+                this.aborted = true;
+
+                // `active_streams` is decremented by `StreamGuard::drop` once tonic
+                // drops this boxed stream after observing the terminal `Err` below,
+                // not here -- avoids double-decrementing when that happens.
+                warn!(
+                    stream_id = %this.stream_id,
+                    client_addr = %this.client_addr,
+                    throughput_per_sec = throughput,
+                    floor_per_sec = this.floor_per_sec,
+                    stalled_for_ms = this.stalled_for.as_millis() as u64,
+                    "Aborting stalled time stream: throughput below configured floor"
+                );
+
+                return Poll::Ready(Some(Err(Status::from(AppError::timeout(
+                    "Stream aborted: sustained throughput fell below the configured floor",
+                )))));
+            }
+        } else {
+            this.stalled_for = Duration::ZERO;
+        }
+
+        Poll::Pending
+    }
+}
+
 #[tonic::async_trait]
 impl greeter_server::Greeter for GreeterService {
     // Associated type for server-side streaming
     type StreamTimeStream = TimeStream;
+    // Associated type for bidirectional streaming greetings
+    type StreamGreetingsStream =
+        Pin<Box<dyn Stream<Item = Result<HelloReply, Status>> + Send + 'static>>;
     /// Handles SayHello RPC requests with domain validation
     ///
     /// Validates the incoming name, generates a greeting, and returns the response.
@@ -46,87 +435,445 @@ impl greeter_server::Greeter for GreeterService {
         &self,
         request: Request<HelloRequest>,
     ) -> std::result::Result<Response<HelloReply>, Status> {
-        // Extract client info and start request timing
+        // Extract client info, start request timing, and look up this method's serve-time budget
         let client_info = extract_client_info(&request);
         let timer = RequestTimer::start(client_info.request_id);
+        let budget = self.method_budgets.budget_for("SayHello");
+        // Honor the client's own deadline (propagated via the `grpc-timeout` header) when
+        // it's tighter than our serve-time budget, so slow handlers are cancelled as soon
+        // as the caller has stopped waiting, not just when the budget eventually elapses.
+        let client_deadline = parse_grpc_timeout_header(&request);
+        let effective_deadline = client_deadline.map_or(budget, |d| d.min(budget));
+
+        let handler = async {
+            // Test-only knob: simulate slow handler work so deadline-cancellation
+            // tests can prove in-flight work actually gets dropped, not just that a
+            // fast handler happens to race a short deadline. Zero (default) is a no-op.
+            if self.greeter_config.artificial_handler_delay_ms > 0 {
+                time::sleep(Duration::from_millis(
+                    self.greeter_config.artificial_handler_delay_ms,
+                ))
+                .await;
+            }
 
-        let hello_request = request.into_inner();
-        let request_name = &hello_request.name;
+            let hello_request = request.into_inner();
+            let request_name = &hello_request.name;
 
-        // Log request start with structured fields
-        info!(
-            request_id = %client_info.request_id,
-            method = "SayHello",
-            client_addr = %client_info.addr,
-            "Processing greeting request"
-        );
+            // Log request start with structured fields
+            info!(
+                request_id = %client_info.request_id,
+                method = "SayHello",
+                client_addr = %client_info.addr,
+                "Processing greeting request"
+            );
+
+            // Domain validation: convert raw request to validated domain type
+            let person_name = match PersonName::new(request_name).with_validation_context(|| {
+                format!("Failed to validate person name '{}'", request_name)
+            }) {
+                Ok(name) => name,
+                Err(app_error) => {
+                    let duration = timer.elapsed_ms();
+
+                    // Record metrics for failed request
+                    self.metrics.record_request(duration);
+                    self.metrics.record_error();
+
+                    warn!(
+                        request_id = %client_info.request_id,
+                        method = "SayHello",
+                        client_addr = %client_info.addr,
+                        error = %app_error,
+                        input = request_name,
+                        duration_ms = duration,
+                        "Request validation failed"
+                    );
+
+                    // Convert AppError to gRPC Status (includes structured error logging)
+                    return Err(Status::from(app_error));
+                }
+            };
 
-        // Domain validation: convert raw request to validated domain type
-        let person_name = match PersonName::new(request_name).with_validation_context(|| {
-            format!("Failed to validate person name '{}'", request_name)
-        }) {
-            Ok(name) => name,
-            Err(app_error) => {
+            // Business logic: generate greeting using domain logic
+            let greeting = GreetingMessage::for_person(&person_name);
+
+            let reply = HelloReply {
+                message: greeting.as_str().to_string(),
+            };
+
+            let duration = timer.elapsed_ms();
+
+            // Record metrics for successful request
+            self.metrics.record_request(duration);
+            self.metrics.record_success();
+
+            // Log successful completion with all context
+            info!(
+                request_id = %client_info.request_id,
+                method = "SayHello",
+                client_addr = %client_info.addr,
+                name = person_name.as_str(),
+                duration_ms = duration,
+                "Successfully processed greeting request"
+            );
+
+            Ok(Response::new(reply))
+        };
+
+        match time::timeout(effective_deadline, handler).await {
+            Ok(result) => {
+                self.method_budgets
+                    .record_observed_ms("SayHello", timer.elapsed_ms());
+                result
+            }
+            Err(_) => {
                 let duration = timer.elapsed_ms();
+                let client_deadline_is_tighter = client_deadline.is_some_and(|d| d < budget);
 
-                // Record metrics for failed request
                 self.metrics.record_request(duration);
                 self.metrics.record_error();
+                self.metrics.record_budget_exceeded("SayHello");
 
                 warn!(
                     request_id = %client_info.request_id,
                     method = "SayHello",
                     client_addr = %client_info.addr,
-                    error = %app_error,
-                    input = request_name,
                     duration_ms = duration,
-                    "Request validation failed"
+                    deadline_ms = effective_deadline.as_millis() as u64,
+                    client_deadline_is_tighter,
+                    "Request exceeded its deadline"
                 );
 
-                // Convert AppError to gRPC Status (includes structured error logging)
-                return Err(Status::from(app_error));
+                let reason = if client_deadline_is_tighter {
+                    "client-requested deadline"
+                } else {
+                    "serve-time budget"
+                };
+
+                Err(Status::from(AppError::timeout(format!(
+                    "SayHello exceeded its {}ms {}",
+                    effective_deadline.as_millis(),
+                    reason
+                ))))
             }
-        };
+        }
+    }
 
-        // Business logic: generate greeting using domain logic
-        let greeting = GreetingMessage::for_person(&person_name);
+    /// Handles StreamTime RPC requests with server-side streaming
+    ///
+    /// Streams current time updates at the request's `interval_millis`, or at
+    /// `greeter.default_interval_seconds` when that is zero, using domain-validated types.
+    /// Each stream connection is tracked with metrics and structured logging including
+    /// stream ID, client address, and connection duration.
+    async fn stream_time(
+        &self,
+        request: Request<TimeRequest>,
+    ) -> Result<Response<Self::StreamTimeStream>, Status> {
+        // Extract client info, resolve the stream ID (reusing one from a valid
+        // resume token, or minting a fresh one) and look up this method's serve-time
+        // budget (covers admission + stream setup, not the stream's lifetime)
+        let client_info = extract_client_info(&request);
+        let (stream_id, start_sequence) =
+            self.resolve_stream_resumption(&request.get_ref().resume_token);
+        let timer = RequestTimer::start(stream_id);
+        let budget = self.method_budgets.budget_for("StreamTime");
+        // Honor the client's own deadline (propagated via the `grpc-timeout` header) when
+        // it's tighter than our serve-time budget, same as `say_hello`.
+        let client_deadline = parse_grpc_timeout_header(&request);
+        let effective_deadline = client_deadline.map_or(budget, |d| d.min(budget));
+
+        // Releases `resolve_stream_resumption`'s claim if setup fails or is
+        // cancelled below, so a resume attempt that merely loses a race against
+        // an admission check or this budget doesn't poison its token forever.
+        let resume_claim_guard = ResumeClaimGuard::new(self.stream_states.clone(), stream_id);
+
+        match time::timeout(
+            effective_deadline,
+            self.setup_time_stream(request, client_info, stream_id, start_sequence),
+        )
+        .await
+        {
+            Ok(result) => {
+                self.method_budgets
+                    .record_observed_ms("StreamTime", timer.elapsed_ms());
+                if result.is_ok() {
+                    resume_claim_guard.disarm();
+                }
+                result
+            }
+            Err(_) => {
+                let client_deadline_is_tighter = client_deadline.is_some_and(|d| d < budget);
+                self.metrics.record_budget_exceeded("StreamTime");
 
-        let reply = HelloReply {
-            message: greeting.as_str().to_string(),
-        };
+                warn!(
+                    stream_id = %stream_id,
+                    method = "StreamTime",
+                    duration_ms = timer.elapsed_ms(),
+                    deadline_ms = effective_deadline.as_millis() as u64,
+                    client_deadline_is_tighter,
+                    "Stream setup exceeded its deadline"
+                );
 
-        let duration = timer.elapsed_ms();
+                let reason = if client_deadline_is_tighter {
+                    "client-requested deadline"
+                } else {
+                    "serve-time budget"
+                };
+
+                Err(Status::from(AppError::timeout(format!(
+                    "StreamTime exceeded its {}ms {} during setup",
+                    effective_deadline.as_millis(),
+                    reason
+                ))))
+            }
+        }
+    }
 
-        // Record metrics for successful request
-        self.metrics.record_request(duration);
-        self.metrics.record_success();
+    /// Handles StreamGreetings RPC requests with bidirectional streaming
+    ///
+    /// Reads a stream of `HelloRequest`s and emits one `HelloReply` per valid
+    /// item, validating each name through `PersonName::new` exactly like
+    /// `say_hello`. gRPC only carries a single terminal `grpc-status` per RPC
+    /// (sent as HTTP/2 trailers), so the first inbound transport error or
+    /// validation failure is yielded as `Err` and ends the call right there —
+    /// tonic's codec stops polling this stream and uses that `Err` as the
+    /// final status, so nothing yielded afterwards would ever reach the
+    /// client. A client that wants to greet many names over one call must
+    /// only send names that pass validation.
+    async fn stream_greetings(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<Self::StreamGreetingsStream>, Status> {
+        let client_info = extract_client_info(&request);
+        let mut inbound = request.into_inner();
+        let metrics = self.metrics.clone();
 
-        // Log successful completion with all context
         info!(
             request_id = %client_info.request_id,
-            method = "SayHello",
+            method = "StreamGreetings",
             client_addr = %client_info.addr,
-            name = person_name.as_str(),
-            duration_ms = duration,
-            "Successfully processed greeting request"
+            "stream_opened"
         );
 
-        Ok(Response::new(reply))
+        let output = async_stream::stream! {
+            while let Some(item) = inbound.next().await {
+                let timer = RequestTimer::start(client_info.request_id);
+
+                let hello_request = match item {
+                    Ok(hello_request) => hello_request,
+                    Err(status) => {
+                        warn!(
+                            request_id = %client_info.request_id,
+                            method = "StreamGreetings",
+                            client_addr = %client_info.addr,
+                            error = %status,
+                            "Inbound stream error"
+                        );
+                        yield Err(status);
+                        break;
+                    }
+                };
+                let request_name = hello_request.name;
+
+                let person_name = match PersonName::new(&request_name).with_validation_context(|| {
+                    format!("Failed to validate person name '{}'", request_name)
+                }) {
+                    Ok(name) => name,
+                    Err(app_error) => {
+                        let duration = timer.elapsed_ms();
+                        metrics.record_request(duration);
+                        metrics.record_error();
+
+                        warn!(
+                            request_id = %client_info.request_id,
+                            method = "StreamGreetings",
+                            client_addr = %client_info.addr,
+                            error = %app_error,
+                            input = request_name,
+                            duration_ms = duration,
+                            "Request validation failed"
+                        );
+
+                        yield Err(Status::from(app_error));
+                        break;
+                    }
+                };
+
+                let greeting = GreetingMessage::for_person(&person_name);
+                let reply = HelloReply {
+                    message: greeting.as_str().to_string(),
+                };
+
+                let duration = timer.elapsed_ms();
+                metrics.record_request(duration);
+                metrics.record_success();
+
+                info!(
+                    request_id = %client_info.request_id,
+                    method = "StreamGreetings",
+                    client_addr = %client_info.addr,
+                    name = person_name.as_str(),
+                    duration_ms = duration,
+                    "Successfully processed greeting request"
+                );
+
+                yield Ok(reply);
+            }
+
+            info!(
+                request_id = %client_info.request_id,
+                method = "StreamGreetings",
+                client_addr = %client_info.addr,
+                "stream_closed"
+            );
+        };
+
+        Ok(Response::new(Box::pin(output)))
     }
+}
 
-    /// Handles StreamTime RPC requests with server-side streaming
+impl GreeterService {
+    /// Resolves a client-supplied `resume_token` (`"<uuid>:<last_seen_sequence>"`)
+    /// against tracked resume state, returning the stream UUID to use and the
+    /// sequence number the next emitted message should carry.
     ///
-    /// Streams current time updates at 1-second intervals using domain-validated types.
-    /// Each stream connection is tracked with metrics and structured logging including
-    /// stream ID, client address, and connection duration.
-    async fn stream_time(
+    /// A token is only honored when it parses and still names a live (i.e.
+    /// not yet TTL-evicted), unclaimed entry in `stream_states`; anything else
+    /// -- an empty token, a malformed one, one whose stream has expired, or one
+    /// already claimed by another in-flight resume of the same token -- mints a
+    /// fresh stream starting at sequence 0.
+    ///
+    /// The claim itself happens here, atomically, via `DashMap::entry` rather
+    /// than a separate check-then-insert in `setup_time_stream` -- two
+    /// concurrent calls racing on the same `resume_token` can't both observe
+    /// the entry as unclaimed and both proceed to resume it, which would let
+    /// them race to overwrite its `next_sequence` later.
+    fn resolve_stream_resumption(&self, resume_token: &str) -> (Uuid, u64) {
+        let parsed = resume_token.rsplit_once(':').and_then(|(uuid_part, seq_part)| {
+            let stream_id = Uuid::parse_str(uuid_part).ok()?;
+            let last_seen: u64 = seq_part.parse().ok()?;
+            Some((stream_id, last_seen))
+        });
+
+        if let Some((stream_id, last_seen)) = parsed {
+            if let dashmap::mapref::entry::Entry::Occupied(mut entry) =
+                self.stream_states.entry(stream_id)
+            {
+                let state = entry.get_mut();
+                if !state.claimed {
+                    state.claimed = true;
+                    return (stream_id, last_seen + 1);
+                }
+            }
+        }
+
+        (Uuid::new_v4(), 0)
+    }
+
+    /// Admits and sets up a `StreamTime` subscription
+    ///
+    /// Split out from `stream_time` so the setup phase can be wrapped in a
+    /// serve-time budget deadline independently of the stream it returns.
+    async fn setup_time_stream(
         &self,
         request: Request<TimeRequest>,
-    ) -> Result<Response<Self::StreamTimeStream>, Status> {
-        // Extract client info and generate unique stream ID
-        let client_info = extract_client_info(&request);
-        let stream_id = Uuid::new_v4();
-        let _timer = RequestTimer::start(stream_id);
+        client_info: crate::utils::ClientInfo,
+        stream_id: Uuid,
+        start_sequence: u64,
+    ) -> Result<Response<TimeStream>, Status> {
+        // Test-only knob: simulate slow stream setup so deadline-cancellation tests
+        // can prove in-flight setup actually gets dropped. Zero (default) is a no-op.
+        if self.greeter_config.artificial_handler_delay_ms > 0 {
+            time::sleep(Duration::from_millis(
+                self.greeter_config.artificial_handler_delay_ms,
+            ))
+            .await;
+        }
+
+        let time_request = request.into_inner();
+
+        // Reject the stream up front if the service-wide concurrent-stream cap
+        // is already saturated. `active_streams` counts live `StreamTime`
+        // streams only; `StreamGreetings` calls are not counted. This is a
+        // second safety net on top of the `max_connections` admission check
+        // below, which also only gates `StreamTime` admissions.
+        if self.metrics.active_streams.load(Ordering::Relaxed)
+            >= self.greeter_config.max_concurrent_streams as u64
+        {
+            self.metrics.record_stream_rejected_concurrency_cap();
+
+            warn!(
+                stream_id = %stream_id,
+                request_id = %client_info.request_id,
+                method = "StreamTime",
+                client_addr = %client_info.addr,
+                max_concurrent_streams = self.greeter_config.max_concurrent_streams,
+                "Rejected time stream: max_concurrent_streams limit reached"
+            );
+
+            return Err(Status::from(AppError::connection_limit_exceeded(
+                "Maximum number of concurrent streams across the service reached",
+            )));
+        }
+
+        // Negotiate the emission interval: an unset (zero) `interval_millis` keeps
+        // the configured default, otherwise it's validated through the same
+        // `StreamInterval` domain type and bounds used everywhere else.
+        let interval = if time_request.interval_millis == 0 {
+            StreamInterval::new(Duration::from_secs(
+                self.greeter_config.default_interval_seconds,
+            ))
+            .unwrap_or_default()
+        } else {
+            match StreamInterval::new(Duration::from_millis(time_request.interval_millis))
+                .with_validation_context(|| {
+                    format!(
+                        "Failed to validate requested stream interval of {}ms",
+                        time_request.interval_millis
+                    )
+                }) {
+                Ok(interval) => interval,
+                Err(app_error) => {
+                    warn!(
+                        stream_id = %stream_id,
+                        request_id = %client_info.request_id,
+                        method = "StreamTime",
+                        client_addr = %client_info.addr,
+                        error = %app_error,
+                        requested_interval_millis = time_request.interval_millis,
+                        "Rejected time stream: invalid requested interval"
+                    );
+
+                    return Err(Status::from(app_error));
+                }
+            }
+        };
+
+        // Zero means "unbounded" for both caps, matching `interval_millis`'s sentinel
+        let max_messages = (time_request.max_messages > 0).then_some(time_request.max_messages);
+        let max_duration = (time_request.max_duration_millis > 0)
+            .then(|| Duration::from_millis(time_request.max_duration_millis));
+
+        // Admit the stream against the configured `max_connections` cap before
+        // creating any stream state; reject over-limit subscribers with RESOURCE_EXHAUSTED.
+        let connection_permit = match self.try_acquire_connection_permit() {
+            Some(permit) => permit,
+            None => {
+                self.metrics.record_stream_rejected_limit();
+
+                warn!(
+                    stream_id = %stream_id,
+                    request_id = %client_info.request_id,
+                    method = "StreamTime",
+                    client_addr = %client_info.addr,
+                    "Rejected time stream: max_connections limit reached"
+                );
+
+                return Err(Status::from(AppError::connection_limit_exceeded(
+                    "Maximum number of concurrent streaming connections reached",
+                )));
+            }
+        };
 
         // Log stream start with structured fields
         info!(
@@ -134,39 +881,114 @@ impl greeter_server::Greeter for GreeterService {
             request_id = %client_info.request_id,
             method = "StreamTime",
             client_addr = %client_info.addr,
-            "Starting time streaming connection"
+            "stream_opened"
         );
 
-        // Record streaming metrics
-        self.metrics.record_stream_started();
+        // Track resume state under this stream's UUID, seeded at the sequence the
+        // caller resumed from (0 for a freshly minted stream). For a resumed
+        // stream this just re-asserts the claim `resolve_stream_resumption`
+        // already took; for a freshly minted one it creates the entry.
+        self.stream_states.insert(
+            stream_id,
+            StreamState {
+                next_sequence: start_sequence,
+                last_active: Instant::now(),
+                claimed: true,
+            },
+        );
 
-        // Create default streaming interval (1 second)
-        let interval = StreamInterval::default();
         let interval_duration = interval.as_duration();
 
         // Create clones for the stream task
         let stream_id_for_map = stream_id;
         let stream_addr_for_map = client_info.addr.clone();
+        let stream_states_for_map = self.stream_states.clone();
+        let messages_sent = Arc::new(AtomicU64::new(0));
+        let mut timestamper = Timestamper::new(self.timestamping_mode);
+
+        // Records `streams_started`/`active_streams` now and reliably decrements
+        // `active_streams` in `Drop`, whenever and however this stream ends.
+        let stream_guard = StreamGuard::new(
+            self.metrics.clone(),
+            stream_id,
+            messages_sent.clone(),
+            stream_states_for_map.clone(),
+            connection_permit,
+        );
 
-        // Create the time streaming generator
-        let time_stream = IntervalStream::new(time::interval(interval_duration)).map(move |_| {
-            let snapshot = TimeSnapshot::now();
-            let response = TimeResponse {
-                timestamp: snapshot.to_rfc3339(),
-            };
-
-            info!(
-                stream_id = %stream_id_for_map,
-                client_addr = %stream_addr_for_map,
-                timestamp = %snapshot.to_rfc3339(),
-                "Streaming time update"
-            );
+        // Create the time streaming generator. `tokio::time::interval`'s first
+        // tick fires immediately regardless of period -- only the second and
+        // later ticks actually wait a full `interval_duration`. Skip that
+        // initial tick so the first emitted item honors the requested/default
+        // interval like callers expect, instead of arriving near-instantly.
+        let time_stream = IntervalStream::new(time::interval(interval_duration))
+            .skip(1)
+            .map(move |_| {
+                // Keep the stream guard alive for as long as the stream is
+                let _stream_guard = &stream_guard;
+
+                let snapshot = timestamper.next();
+                messages_sent.fetch_add(1, Ordering::Relaxed);
+
+                // Advance this stream's resumable sequence counter; if the entry was
+                // swept out from under us (TTL elapsed mid-stream), fall back to 0 --
+                // a reconnect after this point will simply mint a fresh stream.
+                let sequence = stream_states_for_map
+                    .get_mut(&stream_id_for_map)
+                    .map(|mut state| {
+                        let seq = state.next_sequence;
+                        state.next_sequence += 1;
+                        state.last_active = Instant::now();
+                        seq
+                    })
+                    .unwrap_or(0);
+
+                let response = TimeResponse {
+                    timestamp: snapshot.to_rfc3339(),
+                    sequence,
+                    stream_id: stream_id_for_map.to_string(),
+                };
+
+                info!(
+                    stream_id = %stream_id_for_map,
+                    client_addr = %stream_addr_for_map,
+                    sequence = sequence,
+                    timestamp = %snapshot.to_rfc3339(),
+                    "message_sent"
+                );
 
-            Ok(response)
-        });
+                Ok(response)
+            });
 
         // Box the stream for type compatibility
-        let response_stream: TimeStream = Box::pin(time_stream);
+        let mut response_stream: TimeStream = Box::pin(time_stream);
+
+        // Honor the client's requested message/duration caps, if any, so the
+        // stream terminates cleanly instead of running forever
+        if let Some(max_messages) = max_messages {
+            response_stream = Box::pin(response_stream.take(max_messages as usize));
+        }
+
+        if let Some(max_duration) = max_duration {
+            let stream_started_at = Instant::now();
+            response_stream = Box::pin(
+                response_stream
+                    .take_while(move |_| std::future::ready(stream_started_at.elapsed() < max_duration)),
+            );
+        }
+
+        // Wrap with minimum-throughput protection, if configured
+        if let (Some(floor_per_sec), Some(grace_period)) =
+            (self.stall_min_throughput_per_sec, self.stall_grace_period)
+        {
+            response_stream = Box::pin(StalledStreamGuard::new(
+                response_stream,
+                stream_id,
+                client_info.addr.clone(),
+                floor_per_sec,
+                grace_period,
+            ));
+        }
 
         info!(
             stream_id = %stream_id,
@@ -174,6 +996,8 @@ impl greeter_server::Greeter for GreeterService {
             method = "StreamTime",
             client_addr = %client_info.addr,
             interval_ms = interval.as_millis(),
+            max_messages = ?max_messages,
+            max_duration_ms = ?max_duration.map(|d| d.as_millis()),
             "Successfully started time streaming"
         );
 
@@ -186,12 +1010,42 @@ mod tests {
     use super::greeter_server::Greeter;
     use super::*;
     use crate::utils::SimpleMetrics;
+    use std::collections::HashMap;
     use tonic::Request;
 
+    /// Builds a `GreeterService` with the defaults this test module exercises
+    /// against: a generous connection cap, no stall protection, wallclock
+    /// timestamping, the seeded default method budgets (no overrides), and a
+    /// 5-minute resume TTL. Use
+    /// [`test_service_with_config`] when a test needs a non-default
+    /// `GreeterConfig`.
+    fn test_service(metrics: Arc<SimpleMetrics>) -> GreeterService {
+        test_service_with_config(metrics, GreeterConfig::default())
+    }
+
+    /// Like [`test_service`], but with a caller-supplied `GreeterConfig` for
+    /// tests exercising `GreeterConfig`-specific behavior (e.g. stream caps
+    /// or the default interval).
+    fn test_service_with_config(
+        metrics: Arc<SimpleMetrics>,
+        greeter_config: GreeterConfig,
+    ) -> GreeterService {
+        GreeterService::new(
+            metrics,
+            100,
+            None,
+            None,
+            TimestampingMode::Wallclock,
+            Arc::new(MethodBudgets::new(&HashMap::new())),
+            Duration::from_secs(300),
+            greeter_config,
+        )
+    }
+
     #[tokio::test]
     async fn test_say_hello_valid_request() {
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
+        let service = test_service(metrics);
         let request = Request::new(HelloRequest {
             name: "Alice".to_string(),
         });
@@ -205,7 +1059,7 @@ mod tests {
     #[tokio::test]
     async fn test_say_hello_trims_whitespace() {
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
+        let service = test_service(metrics);
         let request = Request::new(HelloRequest {
             name: "  Bob  ".to_string(),
         });
@@ -219,7 +1073,7 @@ mod tests {
     #[tokio::test]
     async fn test_say_hello_empty_name_fails() {
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
+        let service = test_service(metrics);
         let request = Request::new(HelloRequest {
             name: "".to_string(),
         });
@@ -235,7 +1089,7 @@ mod tests {
     #[tokio::test]
     async fn test_say_hello_too_long_name_fails() {
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
+        let service = test_service(metrics);
         let long_name = "a".repeat(101);
         let request = Request::new(HelloRequest { name: long_name });
 
@@ -250,8 +1104,8 @@ mod tests {
     #[tokio::test]
     async fn test_stream_time_starts_successfully() {
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics.clone());
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics.clone());
+        let request = Request::new(TimeRequest::default());
 
         let result = service.stream_time(request).await;
         assert!(result.is_ok());
@@ -276,8 +1130,8 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics);
+        let request = Request::new(TimeRequest::default());
 
         let response = service.stream_time(request).await.unwrap();
         let mut stream = response.into_inner();
@@ -302,8 +1156,8 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics);
+        let request = Request::new(TimeRequest::default());
 
         let response = service.stream_time(request).await.unwrap();
         let mut stream = response.into_inner();
@@ -337,8 +1191,8 @@ mod tests {
         use crate::{StreamInterval, TimeSnapshot};
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics);
+        let request = Request::new(TimeRequest::default());
 
         let result = service.stream_time(request).await;
         assert!(result.is_ok());
@@ -358,8 +1212,8 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics.clone());
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics.clone());
+        let request = Request::new(TimeRequest::default());
 
         let response = service.stream_time(request).await.unwrap();
         let mut stream = response.into_inner();
@@ -391,7 +1245,7 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics.clone());
+        let service = test_service(metrics.clone());
 
         let initial_started = metrics
             .streams_started
@@ -401,7 +1255,7 @@ mod tests {
             .load(std::sync::atomic::Ordering::Relaxed);
 
         // Start a stream
-        let request = Request::new(TimeRequest {});
+        let request = Request::new(TimeRequest::default());
         let response = service.stream_time(request).await.unwrap();
         let mut stream = response.into_inner();
 
@@ -426,19 +1280,20 @@ mod tests {
         // Give time for cleanup
         tokio::time::sleep(Duration::from_millis(100)).await;
 
-        // Active streams should decrease
+        // Active streams should return exactly to its pre-stream value; the
+        // StreamGuard's Drop impl decrements it regardless of how the stream ended
         let after_drop_active = metrics
             .active_streams
             .load(std::sync::atomic::Ordering::Relaxed);
-        assert!(after_drop_active <= after_start_active);
+        assert_eq!(after_drop_active, initial_active);
     }
 
     #[tokio::test]
     async fn test_stream_time_error_handling() {
         // Test that the stream handles internal errors gracefully
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
-        let request = Request::new(TimeRequest {});
+        let service = test_service(metrics);
+        let request = Request::new(TimeRequest::default());
 
         // This test ensures stream creation doesn't fail
         let result = service.stream_time(request).await;
@@ -457,7 +1312,7 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics.clone());
+        let service = test_service(metrics.clone());
 
         // Create multiple concurrent streams
         let mut handles = Vec::new();
@@ -465,7 +1320,7 @@ mod tests {
         for i in 0..3 {
             let service_clone = service.clone();
             let handle: tokio::task::JoinHandle<String> = tokio::spawn(async move {
-                let request = Request::new(TimeRequest {});
+                let request = Request::new(TimeRequest::default());
                 let response = service_clone.stream_time(request).await.unwrap();
                 let mut stream = response.into_inner();
 
@@ -508,10 +1363,10 @@ mod tests {
         use tokio_stream::StreamExt;
 
         let metrics = SimpleMetrics::new();
-        let service = GreeterService::new(metrics);
+        let service = test_service(metrics);
 
         // Create a request with metadata for logging
-        let mut request = Request::new(TimeRequest {});
+        let mut request = Request::new(TimeRequest::default());
         request
             .metadata_mut()
             .insert("x-client-id", "test-client".parse().unwrap());
@@ -527,4 +1382,425 @@ mod tests {
         // Actual log verification would require more complex test infrastructure
         drop(stream);
     }
+
+    #[tokio::test]
+    async fn test_stream_time_fresh_request_starts_at_sequence_zero() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+        let request = Request::new(TimeRequest::default());
+
+        let response = service.stream_time(request).await.unwrap();
+        let mut stream = response.into_inner();
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.sequence, 0);
+        assert!(!first.stream_id.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_valid_resume_token_continues_sequence() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+
+        let first_request = Request::new(TimeRequest::default());
+        let first_response = service.stream_time(first_request).await.unwrap();
+        let mut first_stream = first_response.into_inner();
+        let first_message = first_stream.next().await.unwrap().unwrap();
+        drop(first_stream);
+
+        let resume_token = format!("{}:{}", first_message.stream_id, first_message.sequence);
+        let resumed_request = Request::new(TimeRequest {
+            resume_token: resume_token.clone(),
+            ..Default::default()
+        });
+        let resumed_response = service.stream_time(resumed_request).await.unwrap();
+        let mut resumed_stream = resumed_response.into_inner();
+        let resumed_message = resumed_stream.next().await.unwrap().unwrap();
+
+        assert_eq!(resumed_message.stream_id, first_message.stream_id);
+        assert_eq!(resumed_message.sequence, first_message.sequence + 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_unknown_resume_token_mints_new_stream() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+
+        let request = Request::new(TimeRequest {
+            resume_token: format!("{}:41", Uuid::new_v4()),
+            ..Default::default()
+        });
+        let response = service.stream_time(request).await.unwrap();
+        let mut stream = response.into_inner();
+
+        let message = stream.next().await.unwrap().unwrap();
+        assert_eq!(message.sequence, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_concurrent_resume_of_same_token_claims_exactly_once() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+
+        let first_request = Request::new(TimeRequest::default());
+        let first_response = service.stream_time(first_request).await.unwrap();
+        let mut first_stream = first_response.into_inner();
+        let first_message = first_stream.next().await.unwrap().unwrap();
+        // Releases the resume claim (via `StreamGuard::drop`), so the token
+        // below is up for grabs -- this is the race window two concurrent
+        // resumes of the same token would fall into.
+        drop(first_stream);
+
+        let resume_token = format!("{}:{}", first_message.stream_id, first_message.sequence);
+
+        let (a, b) = tokio::join!(
+            service.stream_time(Request::new(TimeRequest {
+                resume_token: resume_token.clone(),
+                ..Default::default()
+            })),
+            service.stream_time(Request::new(TimeRequest {
+                resume_token,
+                ..Default::default()
+            }))
+        );
+
+        let mut stream_a = a.unwrap().into_inner();
+        let mut stream_b = b.unwrap().into_inner();
+        let message_a = stream_a.next().await.unwrap().unwrap();
+        let message_b = stream_b.next().await.unwrap().unwrap();
+
+        let a_resumed = message_a.stream_id == first_message.stream_id;
+        let b_resumed = message_b.stream_id == first_message.stream_id;
+
+        // Exactly one side may claim `first_message.stream_id`; the other must
+        // mint its own fresh stream rather than racing to share it (and, pre-fix,
+        // colliding on the same `next_sequence` counter).
+        assert_ne!(
+            a_resumed, b_resumed,
+            "exactly one concurrent resume of the same token should claim it"
+        );
+
+        let winner_message = if a_resumed { &message_a } else { &message_b };
+        assert_eq!(winner_message.sequence, first_message.sequence + 1);
+
+        let loser_message = if a_resumed { &message_b } else { &message_a };
+        assert_eq!(loser_message.sequence, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_resume_claim_released_after_failed_admission() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let greeter_config = GreeterConfig {
+            max_concurrent_streams: 1,
+            ..GreeterConfig::default()
+        };
+        let service = test_service_with_config(metrics, greeter_config);
+
+        let first_response = service
+            .stream_time(Request::new(TimeRequest::default()))
+            .await
+            .unwrap();
+        let mut first_stream = first_response.into_inner();
+        let first_message = first_stream.next().await.unwrap().unwrap();
+        // Releases the claim and the one `max_concurrent_streams` slot it
+        // holds, same as a client disconnecting before trying to resume.
+        drop(first_stream);
+
+        let resume_token = format!("{}:{}", first_message.stream_id, first_message.sequence);
+
+        // Saturate `max_concurrent_streams` with an unrelated stream so the
+        // resume attempt below claims the token and then fails admission.
+        let blocker_response = service
+            .stream_time(Request::new(TimeRequest::default()))
+            .await
+            .unwrap();
+        let blocker_stream = blocker_response.into_inner();
+
+        let rejected = service
+            .stream_time(Request::new(TimeRequest {
+                resume_token: resume_token.clone(),
+                ..Default::default()
+            }))
+            .await;
+        let status = rejected
+            .expect_err("resume should be rejected while max_concurrent_streams is saturated");
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+        // Free the cap and retry: the failed admission above must not have
+        // left the token permanently claimed.
+        drop(blocker_stream);
+
+        let resumed_response = service
+            .stream_time(Request::new(TimeRequest {
+                resume_token,
+                ..Default::default()
+            }))
+            .await
+            .expect("token should still be resumable after the earlier failed admission");
+        let mut resumed_stream = resumed_response.into_inner();
+        let resumed_message = resumed_stream.next().await.unwrap().unwrap();
+
+        assert_eq!(resumed_message.stream_id, first_message.stream_id);
+        assert_eq!(resumed_message.sequence, first_message.sequence + 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_guard_restores_active_streams_across_multiple_drops() {
+        use tokio::time::{timeout, Duration};
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics.clone());
+
+        for _ in 0..3 {
+            let request = Request::new(TimeRequest::default());
+            let response = service.stream_time(request).await.unwrap();
+            let mut stream = response.into_inner();
+
+            let message = timeout(Duration::from_secs(2), stream.next()).await;
+            assert!(message.is_ok());
+
+            drop(stream);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        assert_eq!(
+            metrics
+                .active_streams
+                .load(std::sync::atomic::Ordering::Relaxed),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_rejects_interval_below_minimum() {
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+
+        let request = Request::new(TimeRequest {
+            interval_millis: 50,
+            ..Default::default()
+        });
+
+        let result = service.stream_time(request).await;
+        assert!(result.is_err());
+
+        let status = result.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("100ms"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_honors_max_messages_cap() {
+        use tokio::time::{timeout, Duration};
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let service = test_service(metrics);
+
+        let request = Request::new(TimeRequest {
+            interval_millis: 100,
+            max_messages: 2,
+            ..Default::default()
+        });
+
+        let response = service.stream_time(request).await.unwrap();
+        let mut stream = response.into_inner();
+
+        let mut count = 0;
+        while let Ok(Some(_)) = timeout(Duration::from_secs(2), stream.next()).await {
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_rejects_when_concurrent_stream_cap_reached() {
+        let metrics = SimpleMetrics::new();
+        let greeter_config = GreeterConfig {
+            max_concurrent_streams: 1,
+            ..GreeterConfig::default()
+        };
+        let service = test_service_with_config(metrics, greeter_config);
+
+        let first = service
+            .stream_time(Request::new(TimeRequest::default()))
+            .await;
+        assert!(first.is_ok());
+
+        let second = service
+            .stream_time(Request::new(TimeRequest::default()))
+            .await;
+        assert!(second.is_err());
+
+        let status = second.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn test_stream_time_uses_configured_default_interval() {
+        use tokio_stream::StreamExt;
+
+        let metrics = SimpleMetrics::new();
+        let greeter_config = GreeterConfig {
+            default_interval_seconds: 2,
+            ..GreeterConfig::default()
+        };
+        let service = test_service_with_config(metrics, greeter_config);
+
+        let response = service
+            .stream_time(Request::new(TimeRequest::default()))
+            .await
+            .unwrap();
+        let mut stream = response.into_inner();
+
+        let started_at = Instant::now();
+        stream.next().await.unwrap().unwrap();
+        let elapsed = started_at.elapsed();
+
+        // Should honor the configured 2-second default rather than the 1-second
+        // hardcoded one, with generous slack for scheduling jitter.
+        assert!(elapsed >= Duration::from_millis(1500));
+        assert!(elapsed < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_stalled_stream_guard_prune_window_drops_only_stale_entries() {
+        let mut guard = StalledStreamGuard::new(
+            Box::pin(futures::stream::pending::<Result<TimeResponse, Status>>()),
+            Uuid::new_v4(),
+            "127.0.0.1:0".to_string(),
+            1.0,
+            Duration::from_secs(5),
+        );
+
+        let now = Instant::now();
+        guard.window.push_back(now - STALL_WINDOW - Duration::from_secs(1));
+        guard.window.push_back(now - STALL_WINDOW + Duration::from_millis(500));
+        guard.window.push_back(now);
+
+        guard.prune_window(now);
+
+        assert_eq!(guard.window.len(), 2, "only the entry older than STALL_WINDOW should be pruned");
+    }
+
+    #[test]
+    fn test_stalled_stream_guard_throughput_math_matches_window_over_stall_window() {
+        let mut guard = StalledStreamGuard::new(
+            Box::pin(futures::stream::pending::<Result<TimeResponse, Status>>()),
+            Uuid::new_v4(),
+            "127.0.0.1:0".to_string(),
+            2.0,
+            Duration::from_secs(5),
+        );
+
+        let now = Instant::now();
+        for _ in 0..20 {
+            guard.window.push_back(now);
+        }
+
+        let throughput = guard.window.len() as f64 / STALL_WINDOW.as_secs_f64();
+
+        // 20 messages over a 10-second window is 2/sec -- exactly the 2.0/sec
+        // floor configured above, which the guard's `throughput < floor` check
+        // does not treat as stalled.
+        assert_eq!(throughput, 2.0);
+    }
+
+    /// Polls a [`StalledStreamGuard`] roughly every 20ms until it yields an
+    /// item or `overall_timeout` elapses, rather than relying on it to wake a
+    /// stored waker on its own -- keeps the test's timing independent of
+    /// exactly how `recheck_interval` re-arms itself between polls.
+    async fn poll_guard_until_ready(
+        guard: &mut StalledStreamGuard,
+        overall_timeout: Duration,
+    ) -> Option<Result<TimeResponse, Status>> {
+        let deadline = Instant::now() + overall_timeout;
+
+        loop {
+            let polled = std::future::poll_fn(|cx| match Pin::new(&mut *guard).poll_next(cx) {
+                Poll::Ready(item) => Poll::Ready(Some(item)),
+                Poll::Pending => Poll::Ready(None),
+            })
+            .await;
+
+            if let Some(item) = polled {
+                return item;
+            }
+
+            if Instant::now() >= deadline {
+                return None;
+            }
+
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stalled_stream_guard_does_not_abort_a_healthy_stream() {
+        // Produces an item every 100ms (10/sec), comfortably above the 1/sec
+        // floor configured below across several real-time recheck cycles.
+        let inner: TimeStream = Box::pin(
+            IntervalStream::new(time::interval(Duration::from_millis(100))).map(|_| {
+                Ok(TimeResponse {
+                    timestamp: String::new(),
+                    sequence: 0,
+                    stream_id: String::new(),
+                })
+            }),
+        );
+
+        let mut guard = StalledStreamGuard::new(
+            inner,
+            Uuid::new_v4(),
+            "127.0.0.1:0".to_string(),
+            1.0,
+            Duration::from_secs(2),
+        );
+
+        let deadline = Instant::now() + Duration::from_millis(2500);
+        let mut messages_seen = 0;
+        while Instant::now() < deadline {
+            match poll_guard_until_ready(&mut guard, Duration::from_secs(1)).await {
+                Some(Ok(_)) => messages_seen += 1,
+                Some(Err(status)) => panic!("healthy stream was aborted: {status}"),
+                None => panic!("healthy stream produced nothing within 1s"),
+            }
+        }
+
+        assert!(messages_seen > 5, "expected several messages from the healthy stream");
+    }
+
+    #[tokio::test]
+    async fn test_stalled_stream_guard_aborts_a_stalled_stream() {
+        // Never produces anything, so throughput is pinned at 0/sec -- well
+        // below the floor -- from the very first recheck tick.
+        let inner: TimeStream = Box::pin(futures::stream::pending::<Result<TimeResponse, Status>>());
+
+        let mut guard = StalledStreamGuard::new(
+            inner,
+            Uuid::new_v4(),
+            "127.0.0.1:0".to_string(),
+            5.0,
+            Duration::from_millis(100),
+        );
+
+        let result = poll_guard_until_ready(&mut guard, Duration::from_secs(3))
+            .await
+            .expect("expected the stalled stream to be aborted within 3s");
+
+        let status = result.expect_err("expected the stalled stream to be aborted with an error");
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
 }
@@ -1,17 +1,67 @@
+pub mod tracing_interceptor;
+
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::{Request as HyperRequest, Response as HyperResponse, StatusCode};
 use hyper_util::rt::TokioIo;
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::net::TcpListener;
+use tokio::sync::watch;
 use tonic::Request;
 use tracing::{error, info};
 use uuid::Uuid;
 
+/// Per-service serving status, shared between the gRPC health service and the
+/// HTTP `/health` endpoint so both report the same view.
+///
+/// Wraps a [`watch`] channel so the HTTP handler can cheaply read the latest
+/// snapshot without polling tonic_health's own internal state. Callers should
+/// update this alongside the gRPC `HealthReporter` whenever a service's
+/// serving status changes, e.g. from the integration tests' `TestServer`
+/// `set_serving`/`set_not_serving` helpers (`tests/common.rs`).
+#[derive(Debug, Clone)]
+pub struct ServiceHealthStatus {
+    tx: watch::Sender<HashMap<String, bool>>,
+}
+
+impl ServiceHealthStatus {
+    /// Creates a status map with no tracked services yet
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(HashMap::new());
+        Self { tx }
+    }
+
+    /// Marks `service` as serving
+    pub fn set_serving(&self, service: &str) {
+        self.tx.send_modify(|statuses| {
+            statuses.insert(service.to_string(), true);
+        });
+    }
+
+    /// Marks `service` as not serving
+    pub fn set_not_serving(&self, service: &str) {
+        self.tx.send_modify(|statuses| {
+            statuses.insert(service.to_string(), false);
+        });
+    }
+
+    /// `true` only if every tracked service is currently serving (or none are tracked yet)
+    pub fn all_serving(&self) -> bool {
+        self.tx.borrow().values().all(|&serving| serving)
+    }
+}
+
+impl Default for ServiceHealthStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Client information extracted from gRPC requests
 #[derive(Debug, Clone)]
 pub struct ClientInfo {
@@ -25,8 +75,14 @@ pub struct ClientInfo {
 /// If client address is not available, uses "unknown" as fallback.
 pub fn extract_client_info<T>(request: &Request<T>) -> ClientInfo {
     let addr = request
-        .remote_addr()
-        .map(|addr| addr.to_string())
+        .extensions()
+        .get::<crate::proxy_protocol::ProxyProtocolConnectInfo>()
+        .map(|info| {
+            info.proxy_source_addr
+                .unwrap_or(info.peer_addr)
+                .to_string()
+        })
+        .or_else(|| request.remote_addr().map(|addr| addr.to_string()))
         .unwrap_or_else(|| "unknown".to_string());
 
     let request_id = Uuid::new_v4();
@@ -34,6 +90,33 @@ pub fn extract_client_info<T>(request: &Request<T>) -> ClientInfo {
     ClientInfo { addr, request_id }
 }
 
+/// Parses the incoming gRPC `grpc-timeout` header into a [`Duration`]
+///
+/// Follows the gRPC wire format: an ASCII integer followed by a one-character
+/// unit (`H`/`M`/`S` for hours/minutes/seconds, `m`/`u`/`n` for
+/// milli/micro/nanoseconds). tonic doesn't enforce this deadline itself, it's
+/// just ordinary metadata set by `Request::set_timeout` on the client -- callers
+/// that want to honor it (see `GreeterService::say_hello`/`stream_time`)
+/// read it here and race it against their own serve-time budget. Returns `None`
+/// if the header is absent or malformed, which callers should treat the same
+/// as "no client deadline".
+pub fn parse_grpc_timeout_header<T>(request: &Request<T>) -> Option<Duration> {
+    let value = request.metadata().get("grpc-timeout")?.to_str().ok()?;
+    let split_at = value.len().checked_sub(1)?;
+    let (digits, unit) = value.split_at(split_at);
+    let amount: u64 = digits.parse().ok()?;
+
+    match unit {
+        "H" => Some(Duration::from_secs(amount.saturating_mul(3600))),
+        "M" => Some(Duration::from_secs(amount.saturating_mul(60))),
+        "S" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_millis(amount)),
+        "u" => Some(Duration::from_micros(amount)),
+        "n" => Some(Duration::from_nanos(amount)),
+        _ => None,
+    }
+}
+
 /// Utility for tracking request duration
 #[derive(Debug)]
 pub struct RequestTimer {
@@ -66,6 +149,106 @@ impl RequestTimer {
     }
 }
 
+/// Number of log-linear magnitude bands in [`LatencyHistogram`]
+const HISTOGRAM_BANDS: u32 = 16;
+/// Linear sub-buckets per magnitude band
+const HISTOGRAM_SUB_BUCKETS: u64 = 4;
+/// Total bucket count: `HISTOGRAM_BANDS * HISTOGRAM_SUB_BUCKETS`
+const HISTOGRAM_BUCKET_COUNT: usize = (HISTOGRAM_BANDS as usize) * (HISTOGRAM_SUB_BUCKETS as usize);
+
+/// Maps a duration to its histogram bucket index
+///
+/// Buckets grow geometrically: band `b` covers durations roughly in
+/// `[2^b - 1, 2^(b+1) - 2]` milliseconds, split into `HISTOGRAM_SUB_BUCKETS`
+/// equal linear steps. Durations are whole milliseconds, so the finest
+/// resolution near zero is 1 ms and sub-millisecond durations are not told
+/// apart. The top band gives multi-second coverage and also takes every
+/// longer duration, in a fixed 64-slot array.
+fn histogram_bucket_index(duration_ms: u64) -> usize {
+    let d = duration_ms.saturating_add(1);
+    let band = (63 - d.leading_zeros()).min(HISTOGRAM_BANDS - 1);
+    let band_start = 1u64 << band;
+    let step = (band_start / HISTOGRAM_SUB_BUCKETS).max(1);
+    let sub = ((d - band_start) / step).min(HISTOGRAM_SUB_BUCKETS - 1);
+    (band as usize) * (HISTOGRAM_SUB_BUCKETS as usize) + sub as usize
+}
+
+/// Returns the inclusive upper bound (in milliseconds) of the given bucket index
+///
+/// The last bucket also holds every duration above its bound, so for those
+/// samples the returned bound is an understatement.
+fn histogram_bucket_upper_bound_ms(idx: usize) -> u64 {
+    let band = (idx / HISTOGRAM_SUB_BUCKETS as usize) as u32;
+    let sub = (idx % HISTOGRAM_SUB_BUCKETS as usize) as u64;
+    let band_start = 1u64 << band;
+    let step = (band_start / HISTOGRAM_SUB_BUCKETS).max(1);
+    let d_upper = band_start + (sub + 1) * step - 1;
+    d_upper.saturating_sub(1)
+}
+
+/// Lock-free latency histogram using log-linear bucketing
+///
+/// `record` is a single `fetch_add` on the bucket selected by
+/// [`histogram_bucket_index`]; percentile queries snapshot all buckets and
+/// walk the cumulative counts, so both paths stay O(1) and allocation-free
+/// outside of the snapshot itself.
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; HISTOGRAM_BUCKET_COUNT],
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    fn record(&self, duration_ms: u64) {
+        let idx = histogram_bucket_index(duration_ms);
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot all buckets and return the upper bound of the given quantile (0.0..=1.0) in milliseconds
+    pub fn quantile_ms(&self, quantile: f64) -> u64 {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return 0;
+        }
+
+        let target = ((quantile * total as f64).ceil() as u64).max(1);
+        let mut cumulative = 0u64;
+        for (idx, count) in counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return histogram_bucket_upper_bound_ms(idx);
+            }
+        }
+
+        histogram_bucket_upper_bound_ms(HISTOGRAM_BUCKET_COUNT - 1)
+    }
+
+    /// 50th percentile duration in milliseconds
+    pub fn p50_ms(&self) -> u64 {
+        self.quantile_ms(0.50)
+    }
+
+    /// 95th percentile duration in milliseconds
+    pub fn p95_ms(&self) -> u64 {
+        self.quantile_ms(0.95)
+    }
+
+    /// 99th percentile duration in milliseconds
+    pub fn p99_ms(&self) -> u64 {
+        self.quantile_ms(0.99)
+    }
+}
+
 /// Simple metrics collection with atomic counters
 ///
 /// Tracks basic request statistics for observability without external dependencies.
@@ -86,6 +269,16 @@ pub struct SimpleMetrics {
     pub streams_started: AtomicU64,
     /// Total number of streams completed (includes disconnections)
     pub streams_completed: AtomicU64,
+    /// Number of streaming subscriptions rejected due to `max_connections`
+    pub streams_rejected_limit: AtomicU64,
+    /// Number of streaming subscriptions rejected due to `greeter.max_concurrent_streams`
+    pub streams_rejected_concurrency_cap: AtomicU64,
+    /// Request duration histogram, used to derive p50/p95/p99
+    pub latency_histogram: LatencyHistogram,
+    /// Total number of requests that exceeded their per-method serve-time budget
+    pub budget_exceeded_total: AtomicU64,
+    /// Per-method breakdown of `budget_exceeded_total`
+    budget_exceeded_by_method: Mutex<HashMap<String, u64>>,
 }
 
 impl SimpleMetrics {
@@ -99,6 +292,11 @@ impl SimpleMetrics {
             active_streams: AtomicU64::new(0),
             streams_started: AtomicU64::new(0),
             streams_completed: AtomicU64::new(0),
+            streams_rejected_limit: AtomicU64::new(0),
+            streams_rejected_concurrency_cap: AtomicU64::new(0),
+            latency_histogram: LatencyHistogram::new(),
+            budget_exceeded_total: AtomicU64::new(0),
+            budget_exceeded_by_method: Mutex::new(HashMap::new()),
         })
     }
 
@@ -107,6 +305,7 @@ impl SimpleMetrics {
         self.requests_total.fetch_add(1, Ordering::Relaxed);
         self.total_duration_ms
             .fetch_add(duration_ms, Ordering::Relaxed);
+        self.latency_histogram.record(duration_ms);
     }
 
     /// Record a successful request
@@ -131,6 +330,29 @@ impl SimpleMetrics {
         self.active_streams.fetch_sub(1, Ordering::Relaxed);
     }
 
+    /// Record a streaming subscription rejected because `max_connections` was reached
+    pub fn record_stream_rejected_limit(&self) {
+        self.streams_rejected_limit.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a streaming subscription rejected because `greeter.max_concurrent_streams` was reached
+    pub fn record_stream_rejected_concurrency_cap(&self) {
+        self.streams_rejected_concurrency_cap
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a request that exceeded its per-method serve-time budget
+    pub fn record_budget_exceeded(&self, method: &str) {
+        self.budget_exceeded_total.fetch_add(1, Ordering::Relaxed);
+        let mut by_method = self.budget_exceeded_by_method.lock().unwrap();
+        *by_method.entry(method.to_string()).or_insert(0) += 1;
+    }
+
+    /// Snapshot of budget-exceeded counts per method, for logging/metrics export
+    pub fn budget_exceeded_by_method(&self) -> HashMap<String, u64> {
+        self.budget_exceeded_by_method.lock().unwrap().clone()
+    }
+
     /// Log current metrics summary
     pub fn log_summary(&self) {
         let total = self.requests_total.load(Ordering::Relaxed);
@@ -140,6 +362,11 @@ impl SimpleMetrics {
         let active_streams = self.active_streams.load(Ordering::Relaxed);
         let streams_started = self.streams_started.load(Ordering::Relaxed);
         let streams_completed = self.streams_completed.load(Ordering::Relaxed);
+        let streams_rejected_limit = self.streams_rejected_limit.load(Ordering::Relaxed);
+        let streams_rejected_concurrency_cap = self
+            .streams_rejected_concurrency_cap
+            .load(Ordering::Relaxed);
+        let budget_exceeded_total = self.budget_exceeded_total.load(Ordering::Relaxed);
 
         let avg_duration = if total > 0 { total_duration / total } else { 0 };
 
@@ -149,15 +376,25 @@ impl SimpleMetrics {
             0.0
         };
 
+        let p50_duration_ms = self.latency_histogram.p50_ms();
+        let p95_duration_ms = self.latency_histogram.p95_ms();
+        let p99_duration_ms = self.latency_histogram.p99_ms();
+
         info!(
             requests_total = total,
             requests_success = success,
             requests_error = errors,
             success_rate = success_rate,
             avg_duration_ms = avg_duration,
+            p50_duration_ms = p50_duration_ms,
+            p95_duration_ms = p95_duration_ms,
+            p99_duration_ms = p99_duration_ms,
             active_streams = active_streams,
             streams_started = streams_started,
             streams_completed = streams_completed,
+            streams_rejected_limit = streams_rejected_limit,
+            streams_rejected_concurrency_cap = streams_rejected_concurrency_cap,
+            budget_exceeded_total = budget_exceeded_total,
             "Server metrics summary"
         );
     }
@@ -166,31 +403,169 @@ impl SimpleMetrics {
 /// HTTP health check endpoint handler
 ///
 /// Returns JSON health status including service information, timestamp, and version.
-/// Designed for load balancers and monitoring systems.
+/// Designed for load balancers and monitoring systems. Reports `"unhealthy"` with a
+/// 503 status if any tracked service has been flipped to not-serving via
+/// [`ServiceHealthStatus`], mirroring the gRPC health service.
 async fn health_handler(
+    health_status: Arc<ServiceHealthStatus>,
     _req: HyperRequest<hyper::body::Incoming>,
 ) -> Result<HyperResponse<String>, Infallible> {
-    let health_status = serde_json::json!({
-        "status": "healthy",
+    let serving = health_status.all_serving();
+
+    let body = serde_json::json!({
+        "status": if serving { "healthy" } else { "unhealthy" },
         "service": "hello-world-grpc",
         "timestamp": chrono::Utc::now().to_rfc3339(),
         "version": env!("CARGO_PKG_VERSION")
     });
 
+    let status_code = if serving {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
     let response = HyperResponse::builder()
-        .status(StatusCode::OK)
+        .status(status_code)
         .header("content-type", "application/json")
-        .body(health_status.to_string())
+        .body(body.to_string())
         .unwrap();
 
     Ok(response)
 }
 
+/// Render the current counters in Prometheus text exposition format
+///
+/// Emits a `# TYPE` line plus the value for each counter, so the server's
+/// stats can be scraped without parsing logs. `request_duration_ms` is
+/// exposed as a summary: p50/p95/p99 quantile lines from the latency
+/// histogram, plus `_sum`/`_count` lines for the running duration total.
+fn render_prometheus_metrics(metrics: &SimpleMetrics) -> String {
+    let requests_total = metrics.requests_total.load(Ordering::Relaxed);
+    let requests_success = metrics.requests_success.load(Ordering::Relaxed);
+    let requests_error = metrics.requests_error.load(Ordering::Relaxed);
+    let total_duration_ms = metrics.total_duration_ms.load(Ordering::Relaxed);
+    let active_streams = metrics.active_streams.load(Ordering::Relaxed);
+    let streams_started = metrics.streams_started.load(Ordering::Relaxed);
+    let streams_completed = metrics.streams_completed.load(Ordering::Relaxed);
+    let streams_rejected_limit = metrics.streams_rejected_limit.load(Ordering::Relaxed);
+    let streams_rejected_concurrency_cap = metrics
+        .streams_rejected_concurrency_cap
+        .load(Ordering::Relaxed);
+
+    let mut out = String::new();
+
+    out.push_str("# TYPE requests_total counter\n");
+    out.push_str(&format!("requests_total {}\n", requests_total));
+
+    out.push_str("# TYPE requests_success counter\n");
+    out.push_str(&format!("requests_success {}\n", requests_success));
+
+    out.push_str("# TYPE requests_error counter\n");
+    out.push_str(&format!("requests_error {}\n", requests_error));
+
+    out.push_str("# TYPE active_streams gauge\n");
+    out.push_str(&format!("active_streams {}\n", active_streams));
+
+    out.push_str("# TYPE streams_started counter\n");
+    out.push_str(&format!("streams_started {}\n", streams_started));
+
+    out.push_str("# TYPE streams_completed counter\n");
+    out.push_str(&format!("streams_completed {}\n", streams_completed));
+
+    out.push_str("# TYPE streams_rejected_limit counter\n");
+    out.push_str(&format!(
+        "streams_rejected_limit {}\n",
+        streams_rejected_limit
+    ));
+
+    out.push_str("# TYPE streams_rejected_concurrency_cap counter\n");
+    out.push_str(&format!(
+        "streams_rejected_concurrency_cap {}\n",
+        streams_rejected_concurrency_cap
+    ));
+
+    out.push_str("# TYPE budget_exceeded_total counter\n");
+    out.push_str(&format!(
+        "budget_exceeded_total {}\n",
+        metrics.budget_exceeded_total.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# TYPE budget_exceeded_by_method counter\n");
+    for (method, count) in metrics.budget_exceeded_by_method() {
+        out.push_str(&format!(
+            "budget_exceeded_by_method{{method=\"{}\"}} {}\n",
+            method, count
+        ));
+    }
+
+    out.push_str("# TYPE request_duration_ms summary\n");
+    out.push_str(&format!(
+        "request_duration_ms{{quantile=\"0.5\"}} {}\n",
+        metrics.latency_histogram.p50_ms()
+    ));
+    out.push_str(&format!(
+        "request_duration_ms{{quantile=\"0.95\"}} {}\n",
+        metrics.latency_histogram.p95_ms()
+    ));
+    out.push_str(&format!(
+        "request_duration_ms{{quantile=\"0.99\"}} {}\n",
+        metrics.latency_histogram.p99_ms()
+    ));
+    out.push_str(&format!("request_duration_ms_sum {}\n", total_duration_ms));
+    out.push_str(&format!("request_duration_ms_count {}\n", requests_total));
+
+    out
+}
+
+/// Prometheus scrape endpoint handler
+///
+/// Renders the shared [`SimpleMetrics`] counters in Prometheus text exposition
+/// format so any Prometheus/OpenTelemetry scraper can pull server stats.
+async fn metrics_handler(
+    metrics: Arc<SimpleMetrics>,
+    _req: HyperRequest<hyper::body::Incoming>,
+) -> Result<HyperResponse<String>, Infallible> {
+    let body = render_prometheus_metrics(&metrics);
+
+    let response = HyperResponse::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "text/plain; version=0.0.4")
+        .body(body)
+        .unwrap();
+
+    Ok(response)
+}
+
+/// Route an incoming request to the health or metrics handler by path
+///
+/// `/healthz` (and its alias `/health`) returns the existing JSON health
+/// payload; `/metrics` returns
+/// the Prometheus text exposition format; anything else is a 404.
+async fn health_server_router(
+    metrics: Arc<SimpleMetrics>,
+    health_status: Arc<ServiceHealthStatus>,
+    req: HyperRequest<hyper::body::Incoming>,
+) -> Result<HyperResponse<String>, Infallible> {
+    match req.uri().path() {
+        "/healthz" | "/health" => health_handler(health_status, req).await,
+        "/metrics" => metrics_handler(metrics, req).await,
+        _ => Ok(HyperResponse::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(String::new())
+            .unwrap()),
+    }
+}
+
 /// Start HTTP health check server
 ///
-/// Binds to the specified port and serves health check responses.
-/// Runs in a separate async task to avoid blocking the main gRPC server.
-pub async fn start_health_server(port: u16) -> anyhow::Result<()> {
+/// Binds to the specified port and serves health check and Prometheus metrics
+/// responses. Runs in a separate async task to avoid blocking the main gRPC server.
+pub async fn start_health_server(
+    port: u16,
+    metrics: Arc<SimpleMetrics>,
+    health_status: Arc<ServiceHealthStatus>,
+) -> anyhow::Result<()> {
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
     let listener = TcpListener::bind(&addr).await?;
 
@@ -199,10 +574,17 @@ pub async fn start_health_server(port: u16) -> anyhow::Result<()> {
     loop {
         let (stream, _) = listener.accept().await?;
         let io = TokioIo::new(stream);
+        let metrics = metrics.clone();
+        let health_status = health_status.clone();
 
         tokio::task::spawn(async move {
             if let Err(err) = http1::Builder::new()
-                .serve_connection(io, service_fn(health_handler))
+                .serve_connection(
+                    io,
+                    service_fn(move |req| {
+                        health_server_router(metrics.clone(), health_status.clone(), req)
+                    }),
+                )
                 .await
             {
                 error!(error = %err, "Error serving connection");
@@ -210,3 +592,90 @@ pub async fn start_health_server(port: u16) -> anyhow::Result<()> {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_bucket_index_is_in_range() {
+        for duration_ms in [0, 1, 50, 999, 10_000, u64::MAX] {
+            let idx = histogram_bucket_index(duration_ms);
+            assert!(idx < HISTOGRAM_BUCKET_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_histogram_bucket_index_is_monotonically_non_decreasing() {
+        let mut previous = histogram_bucket_index(0);
+        for duration_ms in 1..5000u64 {
+            let idx = histogram_bucket_index(duration_ms);
+            assert!(
+                idx >= previous,
+                "bucket index regressed at duration_ms={duration_ms}: {idx} < {previous}"
+            );
+            previous = idx;
+        }
+    }
+
+    #[test]
+    fn test_histogram_bucket_index_zero_duration_lands_in_first_bucket() {
+        assert_eq!(histogram_bucket_index(0), 0);
+    }
+
+    #[test]
+    fn test_histogram_bucket_upper_bound_covers_its_own_index() {
+        for duration_ms in (0..20_000u64).step_by(7) {
+            let idx = histogram_bucket_index(duration_ms);
+            assert!(
+                duration_ms <= histogram_bucket_upper_bound_ms(idx),
+                "duration_ms={duration_ms} assigned to bucket {idx} but exceeds its upper bound"
+            );
+        }
+    }
+
+    #[test]
+    fn test_quantile_ms_with_no_samples_returns_zero() {
+        let histogram = LatencyHistogram::new();
+        assert_eq!(histogram.quantile_ms(0.50), 0);
+        assert_eq!(histogram.p50_ms(), 0);
+        assert_eq!(histogram.p95_ms(), 0);
+        assert_eq!(histogram.p99_ms(), 0);
+    }
+
+    #[test]
+    fn test_quantile_ms_duration_zero_edge_case() {
+        let histogram = LatencyHistogram::new();
+        histogram.record(0);
+        assert_eq!(histogram.quantile_ms(0.50), histogram_bucket_upper_bound_ms(0));
+    }
+
+    #[test]
+    fn test_quantile_ms_against_synthetic_distribution() {
+        let histogram = LatencyHistogram::new();
+
+        // 100 fast requests around 10ms, then a long tail of slow outliers,
+        // so p50 should land in the fast band while p95/p99 land in the tail.
+        for _ in 0..100 {
+            histogram.record(10);
+        }
+        for _ in 0..5 {
+            histogram.record(500);
+        }
+        for _ in 0..1 {
+            histogram.record(5000);
+        }
+
+        let p50 = histogram.p50_ms();
+        let p95 = histogram.p95_ms();
+        let p99 = histogram.p99_ms();
+
+        assert_eq!(p50, histogram_bucket_upper_bound_ms(histogram_bucket_index(10)));
+        assert_eq!(p95, histogram_bucket_upper_bound_ms(histogram_bucket_index(500)));
+        // 106 samples: the p99 rank is ceil(0.99 * 106) = 105, the last 500ms
+        // sample, so the lone 5000ms outlier lies above p99.
+        assert_eq!(p99, histogram_bucket_upper_bound_ms(histogram_bucket_index(500)));
+        assert!(p50 <= p95);
+        assert!(p95 <= p99);
+    }
+}
@@ -0,0 +1,83 @@
+/// Per-request tracing middleware for the gRPC transport
+///
+/// Wraps every incoming unary call and streaming subscription in a `tracing`
+/// span carrying a stable request ID (UUID v4), the peer address, and the
+/// RPC method name, so the flat `info!` lines emitted deeper in the service
+/// layer can be correlated per client/connection.
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tonic::body::BoxBody;
+use tonic::transport::server::TcpConnectInfo;
+use tower::{Layer, Service};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::proxy_protocol::ProxyProtocolConnectInfo;
+
+/// Tower layer that installs [`RequestTracingService`] in front of the gRPC service
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracingLayer;
+
+impl<S> Layer<S> for RequestTracingLayer {
+    type Service = RequestTracingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestTracingService { inner }
+    }
+}
+
+/// Tower service that opens a request-scoped tracing span around each call
+#[derive(Debug, Clone)]
+pub struct RequestTracingService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for RequestTracingService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let request_id = Uuid::new_v4();
+        let method = req.uri().path().to_string();
+        let peer_addr = req
+            .extensions()
+            .get::<ProxyProtocolConnectInfo>()
+            .map(|info| {
+                info.proxy_source_addr
+                    .unwrap_or(info.peer_addr)
+                    .to_string()
+            })
+            .or_else(|| {
+                req.extensions()
+                    .get::<TcpConnectInfo>()
+                    .and_then(|info| info.remote_addr())
+                    .map(|addr| addr.to_string())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let span = tracing::info_span!(
+            "grpc_request",
+            request_id = %request_id,
+            method = %method,
+            peer_addr = %peer_addr,
+        );
+
+        // Clone the ready service per tower convention (see tower::Service docs);
+        // the clone stands in for `self` while the original remains ready for the next call.
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move { inner.call(req).await }.instrument(span))
+    }
+}
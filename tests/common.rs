@@ -1,20 +1,38 @@
 use anyhow::{Context, Result};
+use futures::Stream;
+use hyper_util::rt::TokioIo;
+use std::collections::HashMap;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
 use std::time::Duration;
-use tokio::net::TcpListener;
+use tempfile::TempDir;
+use tokio::net::{TcpListener, UnixListener, UnixStream};
 use tokio::task::JoinHandle;
-use tonic::transport::{Channel, Endpoint};
+use tokio::time;
+use tokio_stream::wrappers::UnixListenerStream;
+use tonic::transport::{Channel, Endpoint, Uri};
 use tonic_health::server::health_reporter;
+use tower::service_fn;
 use tracing::info;
 
+use hello_world_grpc::budget::MethodBudgets;
 use hello_world_grpc::config::{
-    AppConfig, LogFormat, LogLevel, LoggingConfig, ServerConfig, StreamingConfig,
+    AppConfig, BudgetConfig, GreeterConfig, LogFormat, LogLevel, LoggingConfig, MaxConnections,
+    Port, ServerConfig, StreamingConfig, StreamingIntervalSeconds, StreamingTimeoutSeconds,
+    TlsConfig,
 };
 use hello_world_grpc::services::hello_world::{
-    greeter_client::GreeterClient, greeter_server::GreeterServer, GreeterService, TimeRequest,
-    TimeResponse,
+    greeter_client::GreeterClient, greeter_server::GreeterServer, GreeterService, HelloReply,
+    HelloRequest, TimeRequest, TimeResponse, GREETER_SERVICE_NAME,
 };
-use hello_world_grpc::utils::{start_health_server, SimpleMetrics};
+use hello_world_grpc::utils::{start_health_server, ServiceHealthStatus, SimpleMetrics};
+use hello_world_grpc::TimestampingMode;
+use tonic::transport::{Certificate, ClientTlsConfig, Identity, ServerTlsConfig};
+use tonic_health::server::HealthReporter;
 
 /// Test server for integration testing
 ///
@@ -24,6 +42,8 @@ use hello_world_grpc::utils::{start_health_server, SimpleMetrics};
 pub struct TestServer {
     pub grpc_addr: SocketAddr,
     pub health_port: u16,
+    health_reporter: HealthReporter,
+    service_health: Arc<ServiceHealthStatus>,
     _server_handle: JoinHandle<Result<()>>,
     _health_handle: JoinHandle<Result<()>>,
 }
@@ -34,6 +54,15 @@ impl TestServer {
     /// This creates and starts a full gRPC server with health checks,
     /// similar to the production server but optimized for testing.
     pub async fn start() -> Result<Self> {
+        Self::start_with_greeter_config(GreeterConfig::default()).await
+    }
+
+    /// Start a new test server, using the given `GreeterConfig` instead of the
+    /// default -- e.g. to set `artificial_handler_delay_ms` and drive
+    /// `say_hello`/`stream_time` into their deadline-cancellation path via
+    /// [`run_with_deadline`].
+    #[allow(dead_code)]
+    pub async fn start_with_greeter_config(greeter_config: GreeterConfig) -> Result<Self> {
         // Find available ports
         let grpc_addr = find_available_address().await?;
         let health_port = find_available_port().await?;
@@ -46,7 +75,16 @@ impl TestServer {
 
         // Create metrics and service instances
         let metrics = SimpleMetrics::new();
-        let greeter_service = GreeterService::new(metrics.clone());
+        let greeter_service = GreeterService::new(
+            metrics.clone(),
+            100,
+            None,
+            None,
+            TimestampingMode::Wallclock,
+            Arc::new(MethodBudgets::new(&HashMap::new())),
+            Duration::from_secs(300),
+            greeter_config,
+        );
 
         // Setup gRPC health check service
         let (health_reporter, health_service) = health_reporter();
@@ -54,6 +92,11 @@ impl TestServer {
             .set_serving::<GreeterServer<GreeterService>>()
             .await;
 
+        // Mirrored by the HTTP /health endpoint so it stays consistent with the
+        // gRPC health service without polling it
+        let service_health = Arc::new(ServiceHealthStatus::new());
+        service_health.set_serving(GREETER_SERVICE_NAME);
+
         // Start the gRPC server
         let grpc_addr_clone = grpc_addr;
         let server_handle = tokio::spawn(async move {
@@ -66,8 +109,10 @@ impl TestServer {
         });
 
         // Start the HTTP health server
+        let metrics_for_health = metrics.clone();
+        let service_health_for_http = service_health.clone();
         let health_handle = tokio::spawn(async move {
-            start_health_server(health_port)
+            start_health_server(health_port, metrics_for_health, service_health_for_http)
                 .await
                 .context("HTTP health server failed")
         });
@@ -78,11 +123,49 @@ impl TestServer {
         Ok(TestServer {
             grpc_addr,
             health_port,
+            health_reporter,
+            service_health,
             _server_handle: server_handle,
             _health_handle: health_handle,
         })
     }
 
+    /// Mark the `Greeter` service as `SERVING` again
+    ///
+    /// Reflected by both the gRPC `Health.Check` RPC and the HTTP `/health` endpoint.
+    #[allow(dead_code)]
+    pub async fn set_serving(&self) {
+        self.health_reporter
+            .set_serving::<GreeterServer<GreeterService>>()
+            .await;
+        self.service_health.set_serving(GREETER_SERVICE_NAME);
+    }
+
+    /// Mark the `Greeter` service as `NOT_SERVING`, e.g. to simulate a failing dependency
+    ///
+    /// Reflected by both the gRPC `Health.Check` RPC and the HTTP `/health` endpoint.
+    #[allow(dead_code)]
+    pub async fn set_not_serving(&self) {
+        self.health_reporter
+            .set_not_serving::<GreeterServer<GreeterService>>()
+            .await;
+        self.service_health.set_not_serving(GREETER_SERVICE_NAME);
+    }
+
+    /// Get the underlying health reporter for finer-grained control over serving status
+    #[allow(dead_code)]
+    pub fn health_reporter(&self) -> &HealthReporter {
+        &self.health_reporter
+    }
+
+    /// Aborts the gRPC server task, severing any open connections without a
+    /// graceful shutdown -- used to simulate a server crash/restart for
+    /// reconnect-handling tests (e.g. [`ReconnectingTimeStream`]).
+    #[allow(dead_code)]
+    pub fn shutdown_grpc(&self) {
+        self._server_handle.abort();
+    }
+
     /// Create a gRPC client connected to this test server
     pub async fn grpc_client(&self) -> Result<GreeterClient<Channel>> {
         let endpoint = Endpoint::from_shared(format!("http://{}", self.grpc_addr))
@@ -113,6 +196,189 @@ impl TestServer {
     pub fn health_url(&self) -> String {
         format!("http://127.0.0.1:{}/health", self.health_port)
     }
+
+    /// Get the HTTP Prometheus metrics URL
+    #[allow(dead_code)]
+    pub fn metrics_url(&self) -> String {
+        format!("http://127.0.0.1:{}/metrics", self.health_port)
+    }
+}
+
+/// Test server bound to a Unix domain socket instead of TCP
+///
+/// Exercises the same `GreeterService` over a transport-agnostic integration
+/// test, for deployments where the gRPC server is exposed only as a local
+/// socket. The backing temp directory (and therefore the socket file) is
+/// removed when this is dropped.
+#[allow(dead_code)]
+pub struct UdsTestServer {
+    socket_path: PathBuf,
+    _socket_dir: TempDir,
+    _server_handle: JoinHandle<Result<()>>,
+}
+
+impl UdsTestServer {
+    /// Start a new test server listening on a Unix domain socket under a
+    /// fresh temp directory
+    pub async fn start_uds() -> Result<Self> {
+        let socket_dir = TempDir::new().context("Failed to create temp dir for UDS socket")?;
+        let socket_path = socket_dir.path().join("grpc.sock");
+
+        info!(socket_path = %socket_path.display(), "Starting UDS test server");
+
+        // Create metrics and service instances
+        let metrics = SimpleMetrics::new();
+        let greeter_service = GreeterService::new(
+            metrics.clone(),
+            100,
+            None,
+            None,
+            TimestampingMode::Wallclock,
+            Arc::new(MethodBudgets::new(&HashMap::new())),
+            Duration::from_secs(300),
+            GreeterConfig::default(),
+        );
+
+        // Setup gRPC health check service
+        let (health_reporter, health_service) = health_reporter();
+        health_reporter
+            .set_serving::<GreeterServer<GreeterService>>()
+            .await;
+
+        let uds = UnixListener::bind(&socket_path).context("Failed to bind UDS listener")?;
+        let incoming = UnixListenerStream::new(uds);
+
+        let server_handle = tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(health_service)
+                .add_service(GreeterServer::new(greeter_service))
+                .serve_with_incoming(incoming)
+                .await
+                .context("gRPC UDS server failed")
+        });
+
+        // Wait a bit for the server to start
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Ok(UdsTestServer {
+            socket_path,
+            _socket_dir: socket_dir,
+            _server_handle: server_handle,
+        })
+    }
+
+    /// Create a gRPC client connected to this test server over its Unix socket
+    pub async fn grpc_client(&self) -> Result<GreeterClient<Channel>> {
+        let socket_path = self.socket_path.clone();
+
+        // The URI is never actually resolved over the network -- the
+        // connector below ignores it and always dials the Unix socket -- so
+        // any well-formed placeholder authority works here.
+        let channel = Endpoint::try_from("http://[::]:50051")
+            .context("Failed to create endpoint")?
+            .connect_with_connector(service_fn(move |_: Uri| {
+                let socket_path = socket_path.clone();
+                async move {
+                    let stream = UnixStream::connect(socket_path).await?;
+                    Ok::<_, std::io::Error>(TokioIo::new(stream))
+                }
+            }))
+            .await
+            .context("Failed to connect to UDS test server")?;
+
+        Ok(GreeterClient::new(channel))
+    }
+
+    /// Get the Unix socket path this server is listening on
+    #[allow(dead_code)]
+    pub fn socket_path(&self) -> &std::path::Path {
+        &self.socket_path
+    }
+}
+
+/// Test server configured for TLS, using a self-signed certificate generated
+/// at startup -- so tests can exercise TLS behavior (`say_hello`,
+/// `stream_time`, validation errors) without fixture certs checked into the
+/// repo.
+#[allow(dead_code)]
+pub struct TlsTestServer {
+    pub grpc_addr: SocketAddr,
+    ca_cert_pem: String,
+    _server_handle: JoinHandle<Result<()>>,
+}
+
+impl TlsTestServer {
+    /// Start a new TLS-enabled test server on an available port
+    pub async fn start_tls() -> Result<Self> {
+        let grpc_addr = find_available_address().await?;
+
+        let rcgen::CertifiedKey { cert, signing_key } =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+                .context("Failed to generate self-signed certificate")?;
+        let cert_pem = cert.pem();
+        let key_pem = signing_key.serialize_pem();
+        let ca_cert_pem = cert_pem.clone();
+
+        let tls_config =
+            ServerTlsConfig::new().identity(Identity::from_pem(cert_pem, key_pem));
+
+        // Create metrics and service instances
+        let metrics = SimpleMetrics::new();
+        let greeter_service = GreeterService::new(
+            metrics.clone(),
+            100,
+            None,
+            None,
+            TimestampingMode::Wallclock,
+            Arc::new(MethodBudgets::new(&HashMap::new())),
+            Duration::from_secs(300),
+            GreeterConfig::default(),
+        );
+
+        // Setup gRPC health check service
+        let (health_reporter, health_service) = health_reporter();
+        health_reporter
+            .set_serving::<GreeterServer<GreeterService>>()
+            .await;
+
+        let server_handle = tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .tls_config(tls_config)
+                .context("Failed to configure server TLS")?
+                .add_service(health_service)
+                .add_service(GreeterServer::new(greeter_service))
+                .serve(grpc_addr)
+                .await
+                .context("gRPC TLS server failed")
+        });
+
+        // Wait a bit for the server to start
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Ok(TlsTestServer {
+            grpc_addr,
+            ca_cert_pem,
+            _server_handle: server_handle,
+        })
+    }
+
+    /// Create a gRPC client connected to this test server over TLS
+    pub async fn tls_grpc_client(&self) -> Result<GreeterClient<Channel>> {
+        let tls_config = ClientTlsConfig::new()
+            .ca_certificate(Certificate::from_pem(&self.ca_cert_pem))
+            .domain_name("localhost");
+
+        let channel = Endpoint::from_shared(format!("https://{}", self.grpc_addr))
+            .context("Failed to create endpoint")?
+            .tls_config(tls_config)
+            .context("Failed to configure client TLS")?
+            .connect_timeout(Duration::from_secs(5))
+            .connect()
+            .await
+            .context("Failed to connect to TLS test server")?;
+
+        Ok(GreeterClient::new(channel))
+    }
 }
 
 /// Test configuration with sensible defaults for integration testing
@@ -120,17 +386,31 @@ pub fn test_config() -> AppConfig {
     AppConfig {
         server: ServerConfig {
             grpc_address: "127.0.0.1:50051".to_string(), // Valid address for testing
-            health_port: 8081,                           // Valid port for testing
+            health_port: Port::try_from(8081u16).expect("valid test health port"),
+            shutdown_after_idle_seconds: None,
+            max_decoding_message_size: None,
+            max_encoding_message_size: None,
+            http2_initial_stream_window_size: None,
+            http2_initial_connection_window_size: None,
+            proxy_protocol_trusted_upstreams: Vec::new(),
+            tls: TlsConfig::default(),
         },
         logging: LoggingConfig {
             level: LogLevel::Info,
             format: LogFormat::Pretty, // Pretty format for test output
         },
         streaming: StreamingConfig {
-            interval_seconds: 1,
-            max_connections: 100,
-            timeout_seconds: 300,
+            interval_seconds: StreamingIntervalSeconds::try_from(1u64)
+                .expect("valid test interval_seconds"),
+            max_connections: MaxConnections::try_from(100u32).expect("valid test max_connections"),
+            timeout_seconds: StreamingTimeoutSeconds::try_from(300u64)
+                .expect("valid test timeout_seconds"),
+            stall_min_throughput_per_sec: None,
+            stall_grace_period_seconds: None,
+            resume_ttl_seconds: 300,
         },
+        budgets: BudgetConfig::default(),
+        greeter: GreeterConfig::default(),
     }
 }
 
@@ -204,11 +484,22 @@ impl StreamingClient {
     /// Start a time stream and return the stream handle
     #[allow(dead_code)]
     pub async fn start_time_stream(&mut self) -> Result<tonic::Streaming<TimeResponse>> {
-        let request = tonic::Request::new(TimeRequest {});
+        let request = tonic::Request::new(TimeRequest::default());
         let response = self.client.stream_time(request).await?;
         Ok(response.into_inner())
     }
 
+    /// Start a bidirectional greetings stream for the given names and return the stream handle
+    #[allow(dead_code)]
+    pub async fn start_greetings_stream(
+        &mut self,
+        names: Vec<String>,
+    ) -> Result<tonic::Streaming<HelloReply>> {
+        let outbound = futures::stream::iter(names.into_iter().map(|name| HelloRequest { name }));
+        let response = self.client.stream_greetings(outbound).await?;
+        Ok(response.into_inner())
+    }
+
     /// Get the underlying client for other operations (like say_hello)
     #[allow(dead_code)]
     pub fn client(&mut self) -> &mut GreeterClient<Channel> {
@@ -216,13 +507,152 @@ impl StreamingClient {
     }
 }
 
+/// Connection state for [`ReconnectingTimeStream`]
+///
+/// Held outside the stream body so that dropping/cancelling the stream at any
+/// point (including mid-reconnect) simply drops this state, without leaving
+/// the state machine half-advanced. An in-flight connect task is detached
+/// (its `JoinHandle` is dropped, not aborted) and its result is discarded.
+enum ConnectionState {
+    /// Not currently connected; carries the attempt number for backoff.
+    NotConnected(u32),
+    /// A connect+subscribe task is in flight.
+    Connecting(u32, JoinHandle<Result<tonic::Streaming<TimeResponse>>>),
+    /// Connected and actively receiving messages.
+    Ready(tonic::Streaming<TimeResponse>),
+    /// Waiting out the backoff delay before the next connect attempt.
+    WaitReconnect(u32),
+}
+
+/// Event yielded by [`ReconnectingTimeStream`] so callers can observe reconnects
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum StreamEvent {
+    /// A connect attempt was just started
+    Connecting,
+    /// A message arrived over the active connection
+    Message(TimeResponse),
+    /// The backoff delay `after` following failed attempt `attempt` has
+    /// elapsed; the next connect attempt starts on the following poll
+    Reconnecting { attempt: u32, after: Duration },
+}
+
+/// Base delay for the exponential backoff used between reconnect attempts
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Upper bound on the backoff delay between reconnect attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Computes the exponential backoff delay for a given attempt number (1-based)
+fn reconnect_delay(attempt: u32) -> Duration {
+    let shift = attempt.saturating_sub(1).min(16); // avoid overflow on the shift
+    RECONNECT_BASE_DELAY
+        .saturating_mul(1u32 << shift)
+        .min(RECONNECT_MAX_DELAY)
+}
+
+/// A drop-in time stream that auto-reconnects across transport failures
+///
+/// Wraps [`StreamingClient::connect`] + [`StreamingClient::start_time_stream`] in a
+/// poll-driven state machine, so callers get a stream that survives server
+/// restarts and network interruptions instead of having to drop/reconnect by hand.
+#[allow(dead_code)]
+pub struct ReconnectingTimeStream {
+    addr: SocketAddr,
+    state: ConnectionState,
+    sleep: Option<Pin<Box<time::Sleep>>>,
+}
+
+#[allow(dead_code)]
+impl ReconnectingTimeStream {
+    /// Creates a new reconnecting stream targeting the given server address
+    ///
+    /// The first poll starts the initial connection attempt.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            state: ConnectionState::NotConnected(1),
+            sleep: None,
+        }
+    }
+}
+
+impl Stream for ReconnectingTimeStream {
+    type Item = StreamEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match &mut this.state {
+                ConnectionState::NotConnected(attempt) => {
+                    let attempt = *attempt;
+                    let addr = this.addr;
+
+                    let handle = tokio::spawn(async move {
+                        let mut client = StreamingClient::connect(addr).await?;
+                        client.start_time_stream().await
+                    });
+
+                    this.state = ConnectionState::Connecting(attempt, handle);
+                    return Poll::Ready(Some(StreamEvent::Connecting));
+                }
+                ConnectionState::Connecting(attempt, handle) => {
+                    let attempt = *attempt;
+                    match Pin::new(handle).poll(cx) {
+                        Poll::Ready(Ok(Ok(stream))) => {
+                            this.state = ConnectionState::Ready(stream);
+                            continue;
+                        }
+                        Poll::Ready(Ok(Err(_))) | Poll::Ready(Err(_)) => {
+                            this.sleep = None;
+                            this.state = ConnectionState::WaitReconnect(attempt);
+                            continue;
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                ConnectionState::Ready(stream) => match Pin::new(stream).poll_next(cx) {
+                    Poll::Ready(Some(Ok(message))) => {
+                        return Poll::Ready(Some(StreamEvent::Message(message)));
+                    }
+                    Poll::Ready(Some(Err(_))) | Poll::Ready(None) => {
+                        this.sleep = None;
+                        this.state = ConnectionState::WaitReconnect(1);
+                        continue;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                ConnectionState::WaitReconnect(attempt) => {
+                    let attempt = *attempt;
+                    let delay = reconnect_delay(attempt);
+                    let sleep = this
+                        .sleep
+                        .get_or_insert_with(|| Box::pin(time::sleep(delay)));
+
+                    match sleep.as_mut().poll(cx) {
+                        Poll::Ready(()) => {
+                            this.sleep = None;
+                            this.state = ConnectionState::NotConnected(attempt + 1);
+                            return Poll::Ready(Some(StreamEvent::Reconnecting {
+                                attempt,
+                                after: delay,
+                            }));
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Helper for collecting multiple messages from a stream with timeout
 #[allow(dead_code)]
-pub async fn collect_stream_messages(
-    stream: &mut tonic::Streaming<TimeResponse>,
+pub async fn collect_stream_messages<T>(
+    stream: &mut tonic::Streaming<T>,
     count: usize,
     timeout_per_message: Duration,
-) -> Result<Vec<TimeResponse>> {
+) -> Result<Vec<T>> {
     use tokio::time::timeout;
     use tokio_stream::StreamExt;
 
@@ -253,6 +683,92 @@ pub async fn collect_stream_messages(
     Ok(messages)
 }
 
+/// Helper for collecting a sequence of serving-status transitions from a gRPC
+/// health `Watch` stream, with a per-message timeout -- analogous to
+/// [`collect_stream_messages`], but unwrapping each response down to its
+/// `ServingStatus` for easier assertions on the transition sequence.
+#[allow(dead_code)]
+pub async fn collect_health_transitions(
+    stream: &mut tonic::Streaming<tonic_health::proto::HealthCheckResponse>,
+    count: usize,
+    timeout_per_message: Duration,
+) -> Result<Vec<tonic_health::proto::health_check_response::ServingStatus>> {
+    use tokio::time::timeout;
+    use tokio_stream::StreamExt;
+
+    let mut statuses = Vec::new();
+    for i in 0..count {
+        match timeout(timeout_per_message, stream.next()).await {
+            Ok(Some(Ok(response))) => {
+                let status = tonic_health::proto::health_check_response::ServingStatus::try_from(
+                    response.status,
+                )
+                .unwrap_or(tonic_health::proto::health_check_response::ServingStatus::Unknown);
+                statuses.push(status);
+            }
+            Ok(Some(Err(e))) => {
+                return Err(anyhow::anyhow!(
+                    "Health watch stream error on transition {}: {}",
+                    i,
+                    e
+                ));
+            }
+            Ok(None) => {
+                return Err(anyhow::anyhow!(
+                    "Health watch stream ended unexpectedly after {} transitions",
+                    i
+                ));
+            }
+            Err(_) => {
+                return Err(anyhow::anyhow!(
+                    "Timeout waiting for health transition {} after {}ms",
+                    i,
+                    timeout_per_message.as_millis()
+                ));
+            }
+        }
+    }
+    Ok(statuses)
+}
+
+/// Drives a single RPC against a tightened client deadline and asserts the
+/// server actually cancels it with `DeadlineExceeded`, rather than just
+/// completing quickly enough to race past it.
+///
+/// Sets `deadline` on `request` the same way a real client does via
+/// `Request::set_timeout` (i.e. the `grpc-timeout` header), then invokes
+/// `call`. Pair this with a `TestServer` started via
+/// `start_with_greeter_config` and `artificial_handler_delay_ms` set longer
+/// than `deadline`, so the handler is still sleeping when the deadline fires
+/// and cancellation is actually exercised.
+#[allow(dead_code)]
+pub async fn run_with_deadline<Req, Resp, F, Fut>(
+    mut request: tonic::Request<Req>,
+    deadline: Duration,
+    call: F,
+) -> Result<()>
+where
+    F: FnOnce(tonic::Request<Req>) -> Fut,
+    Fut: Future<Output = std::result::Result<tonic::Response<Resp>, tonic::Status>>,
+{
+    request.set_timeout(deadline);
+
+    match call(request).await {
+        Ok(_) => Err(anyhow::anyhow!(
+            "expected the request to be cancelled with DeadlineExceeded, but it succeeded"
+        )),
+        Err(status) => {
+            anyhow::ensure!(
+                status.code() == tonic::Code::DeadlineExceeded,
+                "expected DeadlineExceeded, got {:?}: {}",
+                status.code(),
+                status.message()
+            );
+            Ok(())
+        }
+    }
+}
+
 /// Helper for testing concurrent streaming clients
 #[allow(dead_code)]
 pub async fn create_concurrent_streaming_clients(
@@ -378,4 +894,34 @@ mod tests {
         let config = test_config();
         assert!(config.validate().is_ok());
     }
+
+    #[tokio::test]
+    async fn test_uds_test_server_say_hello() {
+        let server = UdsTestServer::start_uds().await.unwrap();
+        let mut client = server.grpc_client().await.unwrap();
+
+        let response = client
+            .say_hello(tonic::Request::new(HelloRequest {
+                name: "UDS".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        assert!(response.into_inner().message.contains("UDS"));
+    }
+
+    #[tokio::test]
+    async fn test_tls_test_server_say_hello() {
+        let server = TlsTestServer::start_tls().await.unwrap();
+        let mut client = server.tls_grpc_client().await.unwrap();
+
+        let response = client
+            .say_hello(tonic::Request::new(HelloRequest {
+                name: "TLS".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        assert!(response.into_inner().message.contains("TLS"));
+    }
 }
@@ -2,14 +2,19 @@ use anyhow::Result;
 use std::time::Duration;
 use tonic::Code;
 
-use hello_world_grpc::services::hello_world::{HelloRequest, HelloReply};
+use hello_world_grpc::services::hello_world::{HelloRequest, HelloReply, TimeRequest};
 
 // Import common test utilities
 mod common {
     include!("../common.rs");
 }
 
-use common::{init_test_logging, TestServer};
+use hello_world_grpc::config::GreeterConfig;
+
+use common::{
+    collect_health_transitions, init_test_logging, run_with_deadline, ReconnectingTimeStream,
+    StreamEvent, TestServer,
+};
 
 /// Integration tests for the Hello World gRPC service
 ///
@@ -207,6 +212,145 @@ async fn test_http_health_check_integration() {
     assert!(body["version"].is_string());
 }
 
+#[tokio::test]
+async fn test_http_metrics_integration() {
+    init_test_logging();
+
+    let server = TestServer::start().await.unwrap();
+
+    // Wait a bit for the health/metrics server to fully start
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Make HTTP request to the metrics endpoint
+    let metrics_url = server.metrics_url();
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(&metrics_url)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4"
+    );
+
+    let body = response.text().await.unwrap();
+
+    assert!(body.contains("# TYPE requests_total counter"));
+    assert!(body.contains("requests_total "));
+    assert!(body.contains("# TYPE active_streams gauge"));
+    assert!(body.contains("active_streams "));
+    assert!(body.contains("# TYPE request_duration_ms summary"));
+    assert!(body.contains("request_duration_ms{quantile=\"0.5\"} "));
+    assert!(body.contains("request_duration_ms{quantile=\"0.95\"} "));
+    assert!(body.contains("request_duration_ms{quantile=\"0.99\"} "));
+}
+
+#[tokio::test]
+async fn test_grpc_health_check_reflects_not_serving() {
+    init_test_logging();
+
+    let server = TestServer::start().await.unwrap();
+    server.set_not_serving().await;
+
+    let endpoint = format!("http://{}", server.grpc_address());
+    let channel = tonic::transport::Channel::from_shared(endpoint)
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+
+    let mut health_client = tonic_health::proto::health_client::HealthClient::new(channel);
+
+    let request = tonic_health::proto::HealthCheckRequest {
+        service: "hello_world.Greeter".to_string(),
+    };
+
+    let response = health_client.check(request).await.unwrap();
+    let health_response = response.into_inner();
+
+    assert_eq!(
+        health_response.status,
+        tonic_health::proto::health_check_response::ServingStatus::NotServing as i32
+    );
+}
+
+#[tokio::test]
+async fn test_http_health_check_reflects_not_serving() {
+    init_test_logging();
+
+    let server = TestServer::start().await.unwrap();
+
+    // Wait a bit for the health server to fully start
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    server.set_not_serving().await;
+
+    let health_url = server.health_url();
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(&health_url)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 503);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "unhealthy");
+}
+
+#[tokio::test]
+async fn test_grpc_health_watch_observes_serving_transitions() {
+    init_test_logging();
+
+    let server = TestServer::start().await.unwrap();
+
+    let endpoint = format!("http://{}", server.grpc_address());
+    let channel = tonic::transport::Channel::from_shared(endpoint)
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+
+    let mut health_client = tonic_health::proto::health_client::HealthClient::new(channel);
+
+    let request = tonic_health::proto::HealthCheckRequest {
+        service: "hello_world.Greeter".to_string(),
+    };
+
+    let mut watch_stream = health_client.watch(request).await.unwrap().into_inner();
+
+    // Flip serving status after the watch is established, so the stream
+    // observes the full Serving -> NotServing -> Serving sequence.
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        server.set_not_serving().await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        server.set_serving().await;
+    });
+
+    let transitions =
+        collect_health_transitions(&mut watch_stream, 3, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+    assert_eq!(
+        transitions,
+        vec![
+            tonic_health::proto::health_check_response::ServingStatus::Serving,
+            tonic_health::proto::health_check_response::ServingStatus::NotServing,
+            tonic_health::proto::health_check_response::ServingStatus::Serving,
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_server_startup_and_binding() {
     init_test_logging();
@@ -249,3 +393,95 @@ async fn test_request_with_various_character_sets() {
         assert_eq!(reply.message, expected, "Failed for input: {}", input);
     }
 }
+
+#[tokio::test]
+async fn test_say_hello_cancelled_by_client_deadline() {
+    init_test_logging();
+
+    // The handler sleeps far longer than the deadline we're about to impose,
+    // so this only passes if the server actually cancels in-flight work
+    // rather than letting a fast handler race past a short deadline.
+    let server = TestServer::start_with_greeter_config(GreeterConfig {
+        artificial_handler_delay_ms: 2000,
+        ..GreeterConfig::default()
+    })
+    .await
+    .unwrap();
+    let mut client = server.grpc_client().await.unwrap();
+
+    let request = tonic::Request::new(HelloRequest {
+        name: "Alice".to_string(),
+    });
+
+    run_with_deadline(request, Duration::from_millis(100), |req| async move {
+        client.say_hello(req).await
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_stream_time_setup_cancelled_by_client_deadline() {
+    init_test_logging();
+
+    let server = TestServer::start_with_greeter_config(GreeterConfig {
+        artificial_handler_delay_ms: 2000,
+        ..GreeterConfig::default()
+    })
+    .await
+    .unwrap();
+    let mut client = server.grpc_client().await.unwrap();
+
+    let request = tonic::Request::new(TimeRequest::default());
+
+    run_with_deadline(request, Duration::from_millis(100), |req| async move {
+        client.stream_time(req).await
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_reconnecting_time_stream_observes_connect_then_reconnect_sequence() {
+    use tokio::time::timeout;
+    use tokio_stream::StreamExt;
+
+    init_test_logging();
+
+    let server = TestServer::start().await.unwrap();
+    let mut stream = ReconnectingTimeStream::new(server.grpc_address());
+
+    let connecting = timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("expected a Connecting event")
+        .expect("stream ended unexpectedly");
+    assert!(matches!(connecting, StreamEvent::Connecting));
+
+    let message = timeout(Duration::from_secs(3), stream.next())
+        .await
+        .expect("expected a Message event")
+        .expect("stream ended unexpectedly");
+    assert!(matches!(message, StreamEvent::Message(_)));
+
+    // Kill the server out from under the open stream without a graceful
+    // shutdown, forcing the state machine into its reconnect path.
+    server.shutdown_grpc();
+
+    let reconnecting = timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("expected a Reconnecting event after the server went away")
+        .expect("stream ended unexpectedly");
+    match reconnecting {
+        StreamEvent::Reconnecting { attempt, .. } => assert_eq!(attempt, 1),
+        other => panic!("expected StreamEvent::Reconnecting, got {other:?}"),
+    }
+
+    // The state machine loops back to a fresh connect attempt -- which will
+    // itself fail, since the server is gone, eventually backing off again --
+    // proving the cycle keeps retrying instead of getting stuck.
+    let retry_connecting = timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("expected another Connecting event")
+        .expect("stream ended unexpectedly");
+    assert!(matches!(retry_connecting, StreamEvent::Connecting));
+}
@@ -2,6 +2,7 @@
 use std::time::Duration;
 use tokio::time::timeout;
 use tokio_stream::StreamExt;
+use tonic::Code;
 
 mod common;
 
@@ -398,3 +399,69 @@ async fn test_streaming_network_interruption_simulation() {
     assert!(recovery_message.is_ok());
     assert!(recovery_message.unwrap().is_some());
 }
+
+#[tokio::test]
+async fn test_stream_greetings_multiple_names() {
+    init_test_logging();
+
+    let server = TestServer::start()
+        .await
+        .expect("Failed to start test server");
+
+    let mut client = StreamingClient::from_test_server(&server)
+        .await
+        .expect("Failed to create streaming client");
+
+    let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+    let mut stream = client
+        .start_greetings_stream(names)
+        .await
+        .expect("Failed to start greetings stream");
+
+    let messages = collect_stream_messages(&mut stream, 3, Duration::from_secs(2))
+        .await
+        .expect("Failed to collect greeting replies");
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0].message, "Hello, Alice!");
+    assert_eq!(messages[1].message, "Hello, Bob!");
+    assert_eq!(messages[2].message, "Hello, Carol!");
+}
+
+#[tokio::test]
+async fn test_stream_greetings_invalid_name_ends_the_stream() {
+    init_test_logging();
+
+    let server = TestServer::start()
+        .await
+        .expect("Failed to start test server");
+
+    let mut client = StreamingClient::from_test_server(&server)
+        .await
+        .expect("Failed to create streaming client");
+
+    let names = vec!["".to_string(), "Dave".to_string()];
+    let mut stream = client
+        .start_greetings_stream(names)
+        .await
+        .expect("Failed to start greetings stream");
+
+    // gRPC only carries one terminal status per RPC, so the empty name's
+    // validation failure is the last thing the client ever sees: it becomes
+    // the stream's `Err` and the call ends there, even though "Dave" was
+    // also sent.
+    let first = timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("Timed out waiting for first reply")
+        .expect("Stream ended unexpectedly");
+    let status = first.expect_err("Empty name should fail validation");
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let second = timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("Timed out waiting for stream to close");
+    assert!(
+        second.is_none(),
+        "Stream should have ended after the validation-failure status"
+    );
+}